@@ -1,13 +1,20 @@
 use proc_macro2::{Ident, Span, TokenStream, TokenTree};
 use quote::{quote, quote_spanned, ToTokens};
-use syn::{ItemFn, spanned::Spanned};
+use syn::{
+    parse::{Parse, ParseStream},
+    spanned::Spanned,
+    ItemFn, LitStr, Token,
+};
 
-use crate::error::Error;
+use crate::{
+    error::Error,
+    props::{self, Prop, PropAttr},
+};
 
 
 pub(crate) fn run(attr_args: TokenStream, input: TokenStream) -> Result<TokenStream, Error> {
     // Parse proc macro attribute
-    let component_name = parse_name(attr_args)?;
+    let Args { name, custom_element } = parse_args(attr_args)?;
 
     // ==========================================================================================
     // ===== Parse and verify function
@@ -48,11 +55,15 @@ pub(crate) fn run(attr_args: TokenStream, input: TokenStream) -> Result<TokenStr
         }
     }
 
-    // Check and interpret function inputs
+    // Check and interpret function inputs. References with elided lifetimes
+    // get a named lifetime in the struct, which is added to its generics.
+    let elided_lifetime = syn::Lifetime::new(ELIDED_LIFETIME, Span::call_site());
+    let mut has_elided_lifetimes = false;
     let mut children_arg_visited = false;
+    let mut backend_param = None;
     let mut fn_inputs = Vec::new();
     let mut call_arguments = Vec::new();
-    let mut struct_fields = Vec::new();
+    let mut props = Vec::new();
     for input in fn_def.sig.inputs {
         // Make sure it's not a receiver parameter
         let mut input = match input {
@@ -81,9 +92,16 @@ pub(crate) fn run(attr_args: TokenStream, input: TokenStream) -> Result<TokenStr
         };
 
         let mut is_children_arg = false;
-        for attr in input.attrs.drain_filter(|attr| attr.path.is_ident("domsl")) {
-            match &*attr.parse_args::<Ident>()?.to_string() {
-                "children" => {
+        let mut is_backend_arg = false;
+        let mut default = None;
+        let domsl_attrs = input.attrs.iter()
+            .filter(|attr| attr.path.is_ident("domsl"))
+            .cloned()
+            .collect::<Vec<_>>();
+        input.attrs.retain(|attr| !attr.path.is_ident("domsl"));
+        for attr in domsl_attrs {
+            match attr.parse_args::<PropAttr>()? {
+                PropAttr::Children => {
                     if children_arg_visited {
                         let msg = "second occurance of #[domsl(children)], but it must be used \
                             at most once";
@@ -92,23 +110,40 @@ pub(crate) fn run(attr_args: TokenStream, input: TokenStream) -> Result<TokenStr
                     children_arg_visited = true;
                     is_children_arg = true;
                 }
-                other => {
-                    let msg = format!("unknown domsl attribute '{}'", other);
-                    return Err(Error::new(attr.span(), &msg));
+                PropAttr::Backend => {
+                    if backend_param.is_some() {
+                        let msg = "second occurance of #[domsl(backend)], but it must be used \
+                            at most once";
+                        return Err(Error::new(attr.span(), msg));
+                    }
+                    backend_param = Some(backend_type_param(&input.ty, &fn_def.sig.generics)?);
+                    is_backend_arg = true;
                 }
+                PropAttr::Default(expr) => default = Some(expr),
             }
         }
 
-        let pair = NameAndType { ident: ident.clone(), ty: input.ty };
-
-        let call_arg = if is_children_arg {
+        let call_arg = if is_backend_arg {
+            if default.is_some() || is_children_arg {
+                let msg = "the backend parameter can't have a default value or be the \
+                    children parameter";
+                return Err(Error::new(ident.span(), msg));
+            }
+            quote! { backend }
+        } else if is_children_arg {
+            if default.is_some() {
+                let msg = "the children parameter can't have a default value";
+                return Err(Error::new(ident.span(), msg));
+            }
             quote! { children }
         } else {
-            struct_fields.push(pair.clone());
+            let mut ty = (*input.ty).clone();
+            has_elided_lifetimes |= name_elided_lifetimes(&mut ty, &elided_lifetime)?;
+            props.push(Prop::new(ident.clone(), ty, default));
             quote! { self.#ident }
         };
         call_arguments.push(call_arg);
-        fn_inputs.push(pair);
+        fn_inputs.push(NameAndType { ident, ty: input.ty });
     }
 
 
@@ -116,61 +151,526 @@ pub(crate) fn run(attr_args: TokenStream, input: TokenStream) -> Result<TokenStr
     // ===== Generate the output
     // ===========================================================================================
     let ident = fn_def.sig.ident;
+    let component_name = match name {
+        Some(name) => name,
+        None => name_from_fn(&ident)?,
+    };
     let visibility = fn_def.vis;
     let generics = fn_def.sig.generics;
+    let fn_where_clause = &generics.where_clause;
     let const_ = fn_def.sig.constness;
     let body = fn_def.block;
     let attrs = fn_def.attrs;
 
+    // The backend type parameter belongs to the `Component` impl, not to the
+    // struct.
+    let mut struct_generics = generics.clone();
+    if let Some(backend) = &backend_param {
+        struct_generics.params = struct_generics.params.into_iter()
+            .filter(|param| !matches!(param, syn::GenericParam::Type(t) if t.ident == *backend))
+            .collect();
+        if let Some(where_clause) = &mut struct_generics.where_clause {
+            where_clause.predicates = where_clause.predicates.clone().into_iter()
+                .filter(|pred| !matches!(
+                    pred,
+                    syn::WherePredicate::Type(t) if is_ident_type(&t.bounded_ty, backend)
+                ))
+                .collect();
+        }
+    }
+    if has_elided_lifetimes {
+        let param = syn::GenericParam::Lifetime(syn::LifetimeDef::new(elided_lifetime));
+        struct_generics.params.insert(0, param);
+    }
+    let (_, ty_generics, where_clause) = struct_generics.split_for_impl();
+
+    // Components with a backend parameter work with all backends, the others
+    // only with `web_sys`.
+    let mut impl_generics = struct_generics.clone();
+    let backend = match &backend_param {
+        Some(backend) => {
+            let param = generics.type_params().find(|t| t.ident == *backend);
+            impl_generics.params.push(syn::GenericParam::Type(param.unwrap().clone()));
+            impl_generics.where_clause = generics.where_clause.clone();
+            quote! { #backend }
+        }
+        None => quote! { ::web_sys::Document },
+    };
+    let (impl_generics, _, impl_where_clause) = impl_generics.split_for_impl();
+
     let doc_string = format!("A domsl component. See [`{}`] for more information.", ident);
 
-    Ok(quote! {
+    let custom_element_impl = match custom_element {
+        Some(tag) => {
+            if !struct_generics.params.is_empty() {
+                let msg = "components with generic parameters or references can't be \
+                    custom elements";
+                return Err(Error::new(generics.span(), msg));
+            }
+
+            gen_custom_element(&component_name, &visibility, &tag, &props)
+        }
+        None => quote! {},
+    };
+    // Generic parameters that are only used in the return type, the where
+    // clause or the body don't appear in the fields. They are used by a
+    // `PhantomData` field instead (each wrapped in `PhantomData` itself,
+    // since they might be unsized).
+    let phantom = phantom_field(&struct_generics);
+    let builder = props::gen_builder(
+        &component_name,
+        &visibility,
+        &struct_generics,
+        &props,
+        phantom.as_ref().map(|(ident, _)| ident),
+    );
+    let field_names = props.iter().map(|p| &p.ident);
+    let field_types = props.iter().map(|p| &p.ty);
+    let phantom_def = phantom.map(|(ident, ty)| quote! { #ident: #ty, });
+
+    // The struct is spanned on its name, so that an error about a name
+    // collision points at the function name if the name is derived from it.
+    let struct_def = quote_spanned! {component_name.span()=>
         #[doc = #doc_string]
-        #visibility struct #component_name #generics {
-            #( #struct_fields ,)*
+        #visibility struct #component_name #struct_generics #where_clause {
+            #( #field_names: #field_types ,)*
+            #phantom_def
         }
+    };
 
-        impl ::domsl::Component for #component_name {
+    Ok(quote! {
+        #struct_def
+
+        #builder
+
+        impl #impl_generics ::domsl::Component<#backend> for #component_name #ty_generics
+        #impl_where_clause
+        {
             type Node = #out_type;
             fn render(
                 self,
-                document: &::web_sys::Document,
-                children: Vec<::web_sys::Node>,
+                backend: &#backend,
+                children: Vec<<#backend as ::domsl::Backend>::Node>,
             ) -> Self::Node {
                 #ident( #( #call_arguments ,)* )
             }
         }
 
+        #custom_element_impl
+
         #( #attrs )*
         #visibility #const_ fn #ident #generics ( #(#fn_inputs ,)* ) -> #out_type
+            #fn_where_clause
             #body
     })
 }
 
-/// Parses the name of the component from the attribute token stream. E.g.
-/// `#[domsl::component(Foo)]` (this would return `Foo`).
-fn parse_name(attrs: TokenStream) -> Result<Ident, Error> {
-    // TODO: infer name from function name?
-    let mut iter = attrs.into_iter();
+/// Returns the type parameter `B` of the backend parameter with type `ty`,
+/// which has to be `&B`.
+fn backend_type_param(ty: &syn::Type, generics: &syn::Generics) -> Result<Ident, Error> {
+    if let syn::Type::Reference(syn::TypeReference { elem, mutability: None, .. }) = ty {
+        if let Some(param) = generics.type_params().find(|t| is_ident_type(elem, &t.ident)) {
+            return Ok(param.ident.clone());
+        }
+    }
+
+    let msg = "the backend parameter has to have the type `&B`, where `B` is a type parameter \
+        of the function";
+    Err(Error::new(ty.span(), msg))
+}
+
+/// Returns the name and type of the `PhantomData` field using all type and
+/// lifetime parameters of `generics`, or `None` if there are none.
+fn phantom_field(generics: &syn::Generics) -> Option<(Ident, TokenStream)> {
+    let lifetimes = generics.lifetimes().map(|l| &l.lifetime).collect::<Vec<_>>();
+    let types = generics.type_params().map(|t| &t.ident).collect::<Vec<_>>();
+    if lifetimes.is_empty() && types.is_empty() {
+        return None;
+    }
+
+    let ident = Ident::new("__domsl_phantom", Span::call_site());
+    let ty = quote! {
+        ::std::marker::PhantomData<fn() -> (
+            #( &#lifetimes (), )*
+            #( ::std::marker::PhantomData<#types>, )*
+        )>
+    };
+    Some((ident, ty))
+}
+
+/// Returns whether `ty` is just the identifier `ident`.
+fn is_ident_type(ty: &syn::Type, ident: &Ident) -> bool {
+    matches!(ty, syn::Type::Path(syn::TypePath { qself: None, path }) if path.is_ident(ident))
+}
+
+/// Derives `Component` (and the builder, like `#[derive(Props)]`) for a
+/// struct with `#[derive(Component)]`. The impl is generic over the backend
+/// `B`. The component is rendered by `Self::render(self, backend, children)`
+/// or by the function given with `#[domsl(render = path)]`, which gets a `&B`
+/// and can return anything that converts into `B::Node`.
+pub(crate) fn derive(input: TokenStream) -> Result<TokenStream, Error> {
+    let input: syn::DeriveInput = syn::parse2(input)?;
+    let props = props::struct_props(&input, "Component")?;
+
+    let mut render = None;
+    for attr in input.attrs.iter().filter(|attr| attr.path.is_ident("domsl")) {
+        let RenderAttr(path) = attr.parse_args()?;
+        if render.is_some() {
+            return Err(Error::new(attr.span(), "render function given twice"));
+        }
+        render = Some(path);
+    }
+    let render = render.unwrap_or_else(|| syn::parse_quote!(Self::render));
+
+    let name = &input.ident;
+    let builder = props::gen_builder(name, &input.vis, &input.generics, &props, None);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let call = quote_spanned!(render.span()=> #render(self, backend, children));
+
+    // The backend is an additional type parameter of the impl.
+    let mut component_generics = input.generics.clone();
+    component_generics.params.push(syn::parse_quote!(__DomslBackend: ::domsl::Backend));
+    let (component_impl_generics, _, _) = component_generics.split_for_impl();
+
+    // The render function is called outside of the `Component` impl, as
+    // `Self::render` would resolve to the trait method in there if the struct
+    // has no `render` method.
+    Ok(quote! {
+        #builder
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            #[doc(hidden)]
+            fn __domsl_render<__DomslBackend: ::domsl::Backend>(
+                self,
+                backend: &__DomslBackend,
+                children: Vec<__DomslBackend::Node>,
+            ) -> impl ::std::convert::Into<__DomslBackend::Node> {
+                #call
+            }
+        }
+
+        impl #component_impl_generics ::domsl::Component<__DomslBackend> for #name #ty_generics
+        #where_clause
+        {
+            type Node = __DomslBackend::Node;
+            fn render(
+                self,
+                backend: &__DomslBackend,
+                children: Vec<__DomslBackend::Node>,
+            ) -> Self::Node {
+                ::std::convert::Into::into(self.__domsl_render(backend, children))
+            }
+        }
+    })
+}
+
+/// The `#[domsl(render = path)]` attribute on a struct deriving `Component`.
+struct RenderAttr(syn::Path);
+
+impl Parse for RenderAttr {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let ident = input.parse::<Ident>()?;
+        if ident != "render" {
+            let msg = format!("unknown domsl attribute '{}' (only `render` is supported)", ident);
+            return Err(syn::Error::new(ident.span(), msg));
+        }
+
+        input.parse::<Token![=]>()?;
+        Ok(RenderAttr(input.parse()?))
+    }
+}
+
+/// The name of the lifetime that replaces elided lifetimes in the fields of
+/// the component struct (see `name_elided_lifetimes`).
+const ELIDED_LIFETIME: &str = "'__domsl_elided";
+
+/// Replaces all elided lifetimes in `ty` (in references without lifetime and
+/// `'_`) with `lifetime`, so that `ty` can be used as field type. Returns
+/// whether anything was replaced. Lifetimes in `Fn(&T)` and `fn(&T)` are
+/// left alone, as those are higher-ranked. `impl Trait` results in an error.
+fn name_elided_lifetimes(ty: &mut syn::Type, lifetime: &syn::Lifetime) -> Result<bool, Error> {
+    let replace = |l: &mut syn::Lifetime| {
+        if l.ident == "_" {
+            *l = lifetime.clone();
+            true
+        } else {
+            false
+        }
+    };
+
+    let mut replaced = false;
+    match ty {
+        syn::Type::Reference(r) => {
+            match &mut r.lifetime {
+                Some(l) => replaced |= replace(l),
+                None => {
+                    r.lifetime = Some(lifetime.clone());
+                    replaced = true;
+                }
+            }
+            replaced |= name_elided_lifetimes(&mut r.elem, lifetime)?;
+        }
+        syn::Type::Slice(syn::TypeSlice { elem, .. })
+        | syn::Type::Array(syn::TypeArray { elem, .. })
+        | syn::Type::Ptr(syn::TypePtr { elem, .. })
+        | syn::Type::Paren(syn::TypeParen { elem, .. })
+        | syn::Type::Group(syn::TypeGroup { elem, .. }) => {
+            replaced |= name_elided_lifetimes(elem, lifetime)?;
+        }
+        syn::Type::Tuple(t) => {
+            for elem in &mut t.elems {
+                replaced |= name_elided_lifetimes(elem, lifetime)?;
+            }
+        }
+        syn::Type::Path(p) => {
+            if let Some(qself) = &mut p.qself {
+                replaced |= name_elided_lifetimes(&mut qself.ty, lifetime)?;
+            }
+            for segment in &mut p.path.segments {
+                replaced |= name_elided_lifetimes_in_args(&mut segment.arguments, lifetime)?;
+            }
+        }
+        syn::Type::TraitObject(t) => {
+            for bound in &mut t.bounds {
+                match bound {
+                    syn::TypeParamBound::Lifetime(l) => replaced |= replace(l),
+                    syn::TypeParamBound::Trait(t) => {
+                        for segment in &mut t.path.segments {
+                            replaced |= name_elided_lifetimes_in_args(
+                                &mut segment.arguments,
+                                lifetime,
+                            )?;
+                        }
+                    }
+                }
+            }
+        }
+        syn::Type::ImplTrait(t) => {
+            let msg = "`impl Trait` can't be used as type of a component parameter (use a \
+                generic parameter instead)";
+            return Err(Error::new(t.span(), msg));
+        }
+        _ => {}
+    }
+
+    Ok(replaced)
+}
+
+/// Like `name_elided_lifetimes`, but for the generic arguments of a path
+/// segment (e.g. `<'_, T>` in `Cow<'_, T>`).
+fn name_elided_lifetimes_in_args(
+    args: &mut syn::PathArguments,
+    lifetime: &syn::Lifetime,
+) -> Result<bool, Error> {
+    let args = match args {
+        syn::PathArguments::AngleBracketed(args) => args,
+        _ => return Ok(false),
+    };
 
-    let name = match iter.next() {
-        Some(TokenTree::Ident(ident)) => ident,
-        Some(tt) => {
-            let msg = "component name as identifier (e.g. `#[domsl::component(Foo)]`)";
-            return Err(Error::expected(msg, tt));
+    let mut replaced = false;
+    for arg in &mut args.args {
+        match arg {
+            syn::GenericArgument::Lifetime(l) if l.ident == "_" => {
+                *l = lifetime.clone();
+                replaced = true;
+            }
+            syn::GenericArgument::Type(ty) => {
+                replaced |= name_elided_lifetimes(ty, lifetime)?;
+            }
+            syn::GenericArgument::Binding(binding) => {
+                replaced |= name_elided_lifetimes(&mut binding.ty, lifetime)?;
+            }
+            _ => {}
         }
-        None => {
-            let msg = "missing component name (e.g. `#[domsl::component(MyComponent)]`)";
-            return Err(Error::spanless(msg));
+    }
+
+    Ok(replaced)
+}
+
+/// Generates the `define_custom_element` function, which registers the
+/// component as custom element `tag`. Each field is read from the attribute
+/// with the same name (but `-` instead of `_`). Missing attributes and invalid
+/// values fall back to the default of the prop.
+fn gen_custom_element(
+    component_name: &Ident,
+    visibility: &syn::Visibility,
+    tag: &LitStr,
+    fields: &[Prop],
+) -> TokenStream {
+    let attr_names = fields.iter()
+        .map(|f| f.ident.to_string().trim_start_matches("r#").replace('_', "-"))
+        .collect::<Vec<_>>();
+    let field_inits = fields.iter().zip(&attr_names).map(|(Prop { ident, ty, default }, attr)| {
+        let default = default.clone().unwrap_or_else(|| quote_spanned!(ty.span()=>
+            ::domsl::custom_element::FromAttribute::missing()
+        ));
+        quote! {
+            #ident: ::domsl::custom_element::read_attribute(host, #attr, || #default)
+        }
+    });
+
+    let doc_string = format!(
+        "Registers the custom element `<{}>` which renders this component. Returns \
+            `false` if it was already defined. See `domsl::custom_element` for more \
+            information.",
+        tag.value(),
+    );
+
+    quote! {
+        impl #component_name {
+            #[doc = #doc_string]
+            #visibility fn define_custom_element() -> bool {
+                ::domsl::custom_element::define(
+                    #tag,
+                    &[ #( #attr_names ),* ],
+                    |host: &::web_sys::HtmlElement| #component_name { #( #field_inits ,)* },
+                )
+            }
         }
+    }
+}
+
+/// The arguments of the `#[component]` attribute.
+struct Args {
+    /// The name of the component struct, given as first argument or via
+    /// `rename = "Foo"`. If it's `None`, the name is derived from the name of
+    /// the function.
+    name: Option<Ident>,
+
+    /// The tag name from `custom_element = "x-foo"`.
+    custom_element: Option<LitStr>,
+}
+
+/// Parses the arguments from the attribute token stream. E.g.
+/// `#[domsl::component(Foo, custom_element = "x-foo")]` or
+/// `#[domsl::component(rename = "Foo")]`. All arguments are optional.
+fn parse_args(attrs: TokenStream) -> Result<Args, Error> {
+    let mut args = Args { name: None, custom_element: None };
+
+    let mut tokens = attrs.into_iter().peekable();
+    let mut first = true;
+    while tokens.peek().is_some() {
+        let arg = tokens.by_ref()
+            .take_while(|tt| match tt {
+                TokenTree::Punct(p) => p.as_char() != ',',
+                _ => true,
+            })
+            .collect::<Vec<_>>();
+
+        match (arg.len(), arg.first()) {
+            // Trailing comma
+            (0, _) => {}
+
+            // The component name without `rename`, e.g. `Foo`.
+            (1, Some(TokenTree::Ident(ident))) => {
+                if !first {
+                    let msg = "the component name has to be the first argument";
+                    return Err(Error::new(ident.span(), msg));
+                }
+                args.name = Some(ident.clone());
+            }
+
+            _ => {
+                let tokens = arg.into_iter().collect::<TokenStream>();
+                let nv: syn::MetaNameValue = syn::parse2(tokens.clone()).map_err(|_| {
+                    let msg = "expected `rename = \"Name\"` or `custom_element = \"tag-name\"`";
+                    Error::new(tokens.span(), msg)
+                })?;
+
+                if nv.path.is_ident("rename") {
+                    if args.name.is_some() {
+                        let msg = "component name given twice (as first argument and via \
+                            `rename`)";
+                        return Err(Error::new(nv.path.span(), msg));
+                    }
+                    args.name = Some(parse_rename(nv.lit)?);
+                } else if nv.path.is_ident("custom_element") {
+                    args.custom_element = Some(parse_custom_element(nv.lit)?);
+                } else {
+                    let msg = "unknown argument (only `rename` and `custom_element` are \
+                        supported)";
+                    return Err(Error::new(nv.path.span(), msg));
+                }
+            }
+        }
+
+        first = false;
+    }
+
+    Ok(args)
+}
+
+/// Parses the value of `rename = "Foo"`.
+fn parse_rename(lit: syn::Lit) -> Result<Ident, Error> {
+    match lit {
+        syn::Lit::Str(lit) => lit.parse::<Ident>().map_err(|_| {
+            Error::new(lit.span(), "expected a valid identifier as component name")
+        }),
+        other => Err(Error::new(other.span(), "expected string literal")),
+    }
+}
+
+/// Derives the name of the component struct from the name of the function by
+/// converting it to camel case, e.g. `UserCard` for `user_card`. The name gets
+/// the span of the function name, so that errors about name collisions point
+/// there.
+fn name_from_fn(ident: &Ident) -> Result<Ident, Error> {
+    let name = ident.to_string()
+        .trim_start_matches("r#")
+        .split('_')
+        .flat_map(|word| {
+            let mut chars = word.chars();
+            chars.next().into_iter().flat_map(char::to_uppercase).chain(chars)
+        })
+        .collect::<String>();
+
+    // The name has to be a valid identifier (e.g. not `Self` or starting
+    // with a digit) and must differ from the function name.
+    let hint = "(specify one with `#[domsl::component(rename = \"Name\")]`)";
+    if name.is_empty() {
+        let msg = format!("can't derive a component name from this function name {}", hint);
+        return Err(Error::new(ident.span(), &msg));
+    }
+    if syn::parse_str::<Ident>(&name).is_err() {
+        let msg = format!(
+            "can't derive a component name from this function name: `{}` is not a valid \
+                identifier {}",
+            name,
+            hint,
+        );
+        return Err(Error::new(ident.span(), &msg));
+    }
+    if *ident == name {
+        let msg = format!(
+            "the component name derived from this function name is the function name \
+                itself {}",
+            hint,
+        );
+        return Err(Error::new(ident.span(), &msg));
+    }
+
+    Ok(Ident::new(&name, ident.span()))
+}
+
+/// Parses the value of `custom_element = "x-foo"` and checks that the tag
+/// name is a valid custom element name.
+fn parse_custom_element(lit: syn::Lit) -> Result<LitStr, Error> {
+    let tag = match lit {
+        syn::Lit::Str(lit) => lit,
+        other => return Err(Error::new(other.span(), "expected string literal")),
     };
 
-    if let Some(tt) = iter.next() {
-        let msg = "expected only one identifier, but found this additional token";
-        return Err(Error::new(tt.span(), msg));
+    let value = tag.value();
+    let valid = value.starts_with(|c: char| c.is_ascii_lowercase())
+        && value.contains('-')
+        && value.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+    if !valid {
+        let msg = "invalid custom element name (it has to start with a lowercase letter, \
+            contain a `-` and consist only of lowercase letters, digits and `-`)";
+        return Err(Error::new(tag.span(), msg));
     }
 
-    Ok(name)
+    Ok(tag)
 }
 
 
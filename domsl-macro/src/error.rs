@@ -1,6 +1,11 @@
 use proc_macro2::{Ident, Span, TokenStream, TokenTree};
 use quote::{quote, quote_spanned};
 
+use crate::{
+    html::{Namespace, TagInfo},
+    parse::{Name, Shadow},
+};
+
 
 pub(crate) struct Error {
     error_tokens: TokenStream,
@@ -37,39 +42,75 @@ impl Error {
         Self::spanless("unexpected end of input (forgot to close tag?)")
     }
 
-    pub(crate) fn unknown_tag(tag: &Ident) -> Self {
+    pub(crate) fn unknown_tag(tag: &Name, namespace: Namespace) -> Self {
+        let hint = match namespace {
+            Namespace::Html => " (maybe you meant to capitalize it to call a component?)",
+            _ => "",
+        };
         Self::new(
             tag.span(),
+            &format!("unknown {} tag '<{}>'{}", namespace.name(), tag, hint),
+        )
+    }
+
+    pub(crate) fn invalid_attr(attr: &Name, tag: &TagInfo) -> Self {
+        Self::new(
+            attr.span(),
             &format!(
-                "unknown HTML tag '<{}>' (maybe you meant to capitalize it to call a component?)",
-                tag,
+                "attribute '{}' is not valid on {} tag '<{}>'",
+                attr,
+                tag.namespace.name(),
+                tag.name,
             ),
         )
     }
 
-    pub(crate) fn invalid_attr(attr: &Ident, tag: &str) -> Self {
+    pub(crate) fn unknown_aria_attr(attr: &Name) -> Self {
         Self::new(
             attr.span(),
             &format!(
-                "attribute '{}' is not valid on HTML tag '<{}>'",
+                "unknown ARIA attribute '{}' (it's not a state or property in WAI-ARIA 1.2)",
                 attr,
-                tag,
             ),
         )
     }
-}
 
-impl From<snax::ParseError> for Error {
-    fn from(src: snax::ParseError) -> Self {
-        match src {
-            snax::ParseError::UnexpectedEnd => Self::eof(),
-            snax::ParseError::UnexpectedItem(html_token) => {
-                Self::spanless(&format!("unexpected item {:?}", html_token))
-            }
-            snax::ParseError::UnexpectedToken(tt) => {
-                Self::new(tt.span(), &format!("unexpected token `{}`", tt))
-            }
-        }
+    pub(crate) fn unknown_namespace(namespace: &Ident) -> Self {
+        Self::new(
+            namespace.span(),
+            &format!(
+                "unknown attribute namespace '{}' (only 'on', 'prop', 'class' and 'style' are \
+                    supported)",
+                namespace,
+            ),
+        )
+    }
+
+    pub(crate) fn unknown_css_property(property: &Name) -> Self {
+        Self::new(
+            property.span(),
+            &format!("unknown CSS property '{}' in `style:` directive", property),
+        )
+    }
+
+    pub(crate) fn misplaced_shadow(shadow: &Shadow) -> Self {
+        Self::new(
+            shadow.span,
+            "`<#shadow>` can only be used as direct child of an element",
+        )
+    }
+
+    pub(crate) fn invalid_child(span: Span, child: &str, parent: &TagInfo, allowed: &str) -> Self {
+        Self::new(
+            span,
+            &format!(
+                "{} is not allowed as child of {} tag '<{}>' (allowed children: {})",
+                child,
+                parent.namespace.name(),
+                parent.name,
+                allowed,
+            ),
+        )
     }
 }
 
@@ -0,0 +1,174 @@
+//! Custom elements (like `<my-widget>`) and the registry declaring them.
+//!
+//! Every tag name containing a hyphen is an autonomous custom element. By
+//! default, custom elements have the type `HtmlElement`, allow all attributes
+//! and are transparent (allow the same children as their parent).
+//!
+//! Custom elements can be declared in the TOML file `custom-elements.toml`
+//! next to the `Cargo.toml` of the crate using the macros. Each table
+//! declares one element:
+//!
+//! ```toml
+//! [my-widget]
+//! type = "web_sys::HtmlElement"
+//! attributes = ["label", "size"]
+//!
+//! [fancy-button]
+//! type = "crate::elements::FancyButton"
+//! attributes = ["variant"]
+//! ```
+//!
+//! Declared elements get the given type (which has to be a path that works
+//! wherever the macros are used) and only allow the listed attributes plus
+//! the global ones. `attributes` can be omitted if there are none.
+
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+    env,
+    fs,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+
+use once_cell::sync::Lazy;
+use proc_macro2::TokenStream;
+use quote::quote;
+use serde::Deserialize;
+
+use crate::{
+    error::Error,
+    parse::Name,
+};
+use super::{ContentModel::*, Child::*, Namespace, TagInfo};
+
+
+/// The name of the registry file.
+const REGISTRY_FILE: &str = "custom-elements.toml";
+
+/// Info for all custom elements that are not declared.
+const UNDECLARED: TagInfo = TagInfo {
+    name: Cow::Borrowed(""),
+    ty: Cow::Borrowed("HtmlElement"),
+    namespace: Namespace::Html,
+    allows_any_attribute: true,
+    categories: &[Flow, Phrasing],
+    children: &[Transparent],
+    attributes: Cow::Borrowed(&[]),
+};
+
+/// The parsed registry files, keyed by their path. The macros might be
+/// expanded for several crates in the same process (e.g. in an IDE), and the
+/// files might change in between, so each entry remembers the modification
+/// time of the file it was parsed from.
+static CACHE: Lazy<Mutex<HashMap<PathBuf, CacheEntry>>> = Lazy::new(Default::default);
+
+struct CacheEntry {
+    modified: SystemTime,
+
+    /// All declared custom elements or the error message if the file is
+    /// invalid.
+    registry: Result<Arc<[TagInfo]>, String>,
+}
+
+/// An element declared in the registry file.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Declaration {
+    #[serde(rename = "type")]
+    ty: String,
+    #[serde(default)]
+    attributes: Vec<String>,
+}
+
+/// Returns the info for the custom element `name`, either from the registry
+/// or the default one.
+pub(crate) fn lookup(name: &Name) -> Result<Cow<'static, TagInfo>, Error> {
+    let name_string = name.to_string();
+    if name_string.chars().any(|c| c.is_uppercase()) {
+        let msg = "custom element names must not contain uppercase letters";
+        return Err(Error::new(name.span(), msg));
+    }
+
+    let registry = registry()?;
+    let info = registry.iter()
+        .find(|info| info.name == name_string)
+        .cloned()
+        .unwrap_or_else(|| TagInfo { name: Cow::Owned(name_string), ..UNDECLARED });
+    Ok(Cow::Owned(info))
+}
+
+/// Returns an item that makes cargo rebuild the crate if the registry file
+/// changes, or nothing if there is no registry file. The macros include this
+/// in their output.
+///
+/// Note that this can't track a registry file that doesn't exist yet: after
+/// creating it, the crate has to be rebuilt (e.g. by touching a source file)
+/// for the declarations to take effect.
+pub(crate) fn track_registry() -> TokenStream {
+    match registry_path().filter(|path| path.is_file()) {
+        Some(path) => {
+            let path = path.to_string_lossy();
+            quote! { const _: &[u8] = ::std::include_bytes!(#path); }
+        }
+        None => quote! {},
+    }
+}
+
+/// Returns the path of the registry file of the crate using the macros.
+fn registry_path() -> Option<PathBuf> {
+    env::var_os("CARGO_MANIFEST_DIR").map(|dir| PathBuf::from(dir).join(REGISTRY_FILE))
+}
+
+/// Returns all declared custom elements. Empty if there is no registry file.
+fn registry() -> Result<Arc<[TagInfo]>, Error> {
+    let path = registry_path();
+    let modified = path.as_ref()
+        .and_then(|path| fs::metadata(path).ok())
+        .filter(|metadata| metadata.is_file())
+        .and_then(|metadata| metadata.modified().ok());
+    let (path, modified) = match (path, modified) {
+        (Some(path), Some(modified)) => (path, modified),
+        _ => return Ok(Arc::new([])),
+    };
+
+    let mut cache = CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    let is_fresh = cache.get(&path).map_or(false, |entry| entry.modified == modified);
+    if !is_fresh {
+        let registry = fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| parse_registry(&content))
+            .map(Arc::from);
+        cache.insert(path.clone(), CacheEntry { modified, registry });
+    }
+
+    cache[&path].registry.clone().map_err(|msg| {
+        Error::spanless(&format!("invalid `{}`: {}", REGISTRY_FILE, msg))
+    })
+}
+
+fn parse_registry(content: &str) -> Result<Vec<TagInfo>, String> {
+    let declarations: BTreeMap<String, Declaration> = toml::from_str(content)
+        .map_err(|e| e.to_string())?;
+
+    declarations.into_iter().map(|(name, Declaration { ty, attributes })| {
+        if !name.contains('-') {
+            return Err(format!("custom element name `{}` does not contain `-`", name));
+        }
+        if name.chars().any(|c| c.is_uppercase()) {
+            return Err(format!("custom element name `{}` contains uppercase letters", name));
+        }
+        if !ty.contains("::") {
+            return Err(format!("type of `{}` has to be a path, e.g. `web_sys::{}`", name, ty));
+        }
+
+        Ok(TagInfo {
+            name: Cow::Owned(name),
+            ty: Cow::Owned(ty),
+            allows_any_attribute: false,
+            attributes: attributes.into_iter().map(Cow::Owned).collect(),
+            ..UNDECLARED
+        })
+    }).collect()
+}
@@ -0,0 +1,73 @@
+//! This module holds data about the events that can be fired on HTML
+//! elements.
+
+/// Maps event names to the name of the `web_sys` type of the event object.
+/// Events that are not listed here get the general `Event`.
+///
+/// The list is based on the event index of the HTML standard and the UI
+/// Events standard:
+///
+///     https://html.spec.whatwg.org/#events-2
+///     https://w3c.github.io/uievents/#events-module
+///
+/// When adding types here, make sure to also enable the corresponding `web-sys`
+/// feature in `domsl`'s `Cargo.toml`.
+///
+/// `input` is deliberately missing: it's only an `InputEvent` for text
+/// inputs, but a plain `Event` for e.g. `<select>` or checkboxes, so casting
+/// it to `InputEvent` would be wrong there.
+pub(crate) const EVENT_TYPES: &[(&str, &str)] = &[
+    ("animationcancel", "AnimationEvent"),
+    ("animationend", "AnimationEvent"),
+    ("animationiteration", "AnimationEvent"),
+    ("animationstart", "AnimationEvent"),
+    ("auxclick", "MouseEvent"),
+    ("beforeinput", "InputEvent"),
+    ("blur", "FocusEvent"),
+    ("click", "MouseEvent"),
+    ("compositionend", "CompositionEvent"),
+    ("compositionstart", "CompositionEvent"),
+    ("compositionupdate", "CompositionEvent"),
+    ("contextmenu", "MouseEvent"),
+    ("copy", "ClipboardEvent"),
+    ("cut", "ClipboardEvent"),
+    ("dblclick", "MouseEvent"),
+    ("drag", "DragEvent"),
+    ("dragend", "DragEvent"),
+    ("dragenter", "DragEvent"),
+    ("dragleave", "DragEvent"),
+    ("dragover", "DragEvent"),
+    ("dragstart", "DragEvent"),
+    ("drop", "DragEvent"),
+    ("focus", "FocusEvent"),
+    ("focusin", "FocusEvent"),
+    ("focusout", "FocusEvent"),
+    ("keydown", "KeyboardEvent"),
+    ("keypress", "KeyboardEvent"),
+    ("keyup", "KeyboardEvent"),
+    ("mousedown", "MouseEvent"),
+    ("mouseenter", "MouseEvent"),
+    ("mouseleave", "MouseEvent"),
+    ("mousemove", "MouseEvent"),
+    ("mouseout", "MouseEvent"),
+    ("mouseover", "MouseEvent"),
+    ("mouseup", "MouseEvent"),
+    ("paste", "ClipboardEvent"),
+    ("pointercancel", "PointerEvent"),
+    ("pointerdown", "PointerEvent"),
+    ("pointerenter", "PointerEvent"),
+    ("pointerleave", "PointerEvent"),
+    ("pointermove", "PointerEvent"),
+    ("pointerout", "PointerEvent"),
+    ("pointerover", "PointerEvent"),
+    ("pointerup", "PointerEvent"),
+    ("touchcancel", "TouchEvent"),
+    ("touchend", "TouchEvent"),
+    ("touchmove", "TouchEvent"),
+    ("touchstart", "TouchEvent"),
+    ("transitioncancel", "TransitionEvent"),
+    ("transitionend", "TransitionEvent"),
+    ("transitionrun", "TransitionEvent"),
+    ("transitionstart", "TransitionEvent"),
+    ("wheel", "WheelEvent"),
+];
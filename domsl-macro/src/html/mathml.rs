@@ -0,0 +1,192 @@
+//! This module holds data about the elements defined in MathML Core.
+
+use super::{ContentModel::*, Child::*, Namespace::MathMl as MathMlNamespace, TagInfo};
+
+/// The global attributes of MathML Core:
+///
+///     https://w3c.github.io/mathml-core/#global-attributes
+///
+/// `role` is added, as it is a global attribute according to WAI-ARIA.
+pub(crate) const MATHML_GLOBAL_ATTRIBUTES: &[&str] = &[
+    "autofocus",
+    "class",
+    "dir",
+    "displaystyle",
+    "id",
+    "mathbackground",
+    "mathcolor",
+    "mathsize",
+    "nonce",
+    "role",
+    "scriptlevel",
+    "style",
+    "tabindex",
+];
+
+// This data is based on the elements of MathML Core:
+//
+//     https://w3c.github.io/mathml-core/#presentation-markup
+//
+// `web_sys` only has a single type for all MathML elements. Simplifications:
+//
+// - `annotation-xml` is missing, as hyphenated tag names are custom elements.
+// - The number of children (e.g. exactly two for `mfrac`) is not checked.
+// - Elements that are "MathML content" in the standard have the category
+//   `MathMl`. Text is only allowed in token elements (like `mi` and `mn`).
+def_tags!(MATHML_TAG_INFOS, MathMlNamespace; [
+    // Syntax:
+    // tag: Type => { [categories...], [children...], [attributes...] }
+    annotation: MathMlElement => {
+        [],
+        [Text],
+        ["encoding"],
+    },
+    maction: MathMlElement => {
+        [MathMl],
+        [Model(MathMl)],
+        ["actiontype", "selection"],
+    },
+    math: MathMlElement => {
+        [Flow, Phrasing],
+        [Model(MathMl)],
+        ["display", "alttext"],
+    },
+    merror: MathMlElement => {
+        [MathMl],
+        [Model(MathMl)],
+        [],
+    },
+    mfrac: MathMlElement => {
+        [MathMl],
+        [Model(MathMl)],
+        ["linethickness"],
+    },
+    mi: MathMlElement => {
+        [MathMl],
+        [Text],
+        ["mathvariant"],
+    },
+    mmultiscripts: MathMlElement => {
+        [MathMl],
+        [Model(MathMl), Tag("mprescripts")],
+        [],
+    },
+    mn: MathMlElement => {
+        [MathMl],
+        [Text],
+        [],
+    },
+    mo: MathMlElement => {
+        [MathMl],
+        [Text],
+        [
+            "form", "fence", "separator", "lspace", "rspace", "stretchy", "symmetric", "maxsize",
+            "minsize", "largeop", "movablelimits",
+        ],
+    },
+    mover: MathMlElement => {
+        [MathMl],
+        [Model(MathMl)],
+        ["accent"],
+    },
+    mpadded: MathMlElement => {
+        [MathMl],
+        [Model(MathMl)],
+        ["width", "height", "depth", "lspace", "voffset"],
+    },
+    mphantom: MathMlElement => {
+        [MathMl],
+        [Model(MathMl)],
+        [],
+    },
+    mprescripts: MathMlElement => {
+        [],
+        [],
+        [],
+    },
+    mroot: MathMlElement => {
+        [MathMl],
+        [Model(MathMl)],
+        [],
+    },
+    mrow: MathMlElement => {
+        [MathMl],
+        [Model(MathMl)],
+        [],
+    },
+    ms: MathMlElement => {
+        [MathMl],
+        [Text],
+        [],
+    },
+    mspace: MathMlElement => {
+        [MathMl],
+        [],
+        ["width", "height", "depth"],
+    },
+    msqrt: MathMlElement => {
+        [MathMl],
+        [Model(MathMl)],
+        [],
+    },
+    mstyle: MathMlElement => {
+        [MathMl],
+        [Model(MathMl)],
+        [],
+    },
+    msub: MathMlElement => {
+        [MathMl],
+        [Model(MathMl)],
+        [],
+    },
+    msubsup: MathMlElement => {
+        [MathMl],
+        [Model(MathMl)],
+        [],
+    },
+    msup: MathMlElement => {
+        [MathMl],
+        [Model(MathMl)],
+        [],
+    },
+    mtable: MathMlElement => {
+        [MathMl],
+        [Tag("mtr")],
+        [],
+    },
+    mtd: MathMlElement => {
+        [],
+        [Model(MathMl)],
+        ["columnspan", "rowspan"],
+    },
+    mtext: MathMlElement => {
+        [MathMl],
+        [Text],
+        [],
+    },
+    mtr: MathMlElement => {
+        [],
+        [Tag("mtd")],
+        [],
+    },
+    munder: MathMlElement => {
+        [MathMl],
+        [Model(MathMl)],
+        ["accentunder"],
+    },
+    munderover: MathMlElement => {
+        [MathMl],
+        [Model(MathMl)],
+        ["accent", "accentunder"],
+    },
+    none: MathMlElement => {
+        [MathMl],
+        [],
+        [],
+    },
+    semantics: MathMlElement => {
+        [MathMl],
+        [Model(MathMl), Tag("annotation")],
+        [],
+    },
+]);
@@ -1,22 +1,131 @@
+// The tag data is specified in a custom syntax so that we can generate
+// different structures out of it. This has to be defined before the modules
+// using it.
+macro_rules! def_tags {
+    ($const_name:ident, $namespace:ident; [ $(
+        $name:ident: $ty:ident => {
+            [ $($category:ident),* $(,)? ],
+            [ $($child:expr),* $(,)? ],
+            [ $($attribute:literal),* $(,)? ],
+        },
+    )* ]) => {
+        pub(crate) const $const_name: &[TagInfo] = &[ $(
+            TagInfo {
+                name: ::std::borrow::Cow::Borrowed(stringify!($name)),
+                ty: ::std::borrow::Cow::Borrowed(stringify!($ty)),
+                namespace: $namespace,
+                allows_any_attribute: false,
+                categories: &[ $( $category ),* ],
+                children: &[ $( $child ),* ],
+                attributes: ::std::borrow::Cow::Borrowed(&[
+                    $( ::std::borrow::Cow::Borrowed($attribute) ),*
+                ]),
+            },
+        )* ];
+    };
+}
+
+mod aria;
+mod css;
+pub(crate) mod custom;
+mod events;
+mod mathml;
+mod svg;
 mod tags;
 
-use proc_macro2::{Ident, Span};
+use std::borrow::Cow;
+
+use proc_macro2::{Ident, Span, TokenStream, TokenTree};
+use quote::quote;
 
 use crate::{
     error::Error,
+    parse::Name,
 };
-pub(crate) use tags::{GLOBAL_ATTRIBUTES, TAG_INFOS};
+pub(crate) use aria::ARIA_ATTRIBUTES;
+pub(crate) use css::CSS_PROPERTIES;
+pub(crate) use events::EVENT_TYPES;
+pub(crate) use mathml::{MATHML_GLOBAL_ATTRIBUTES, MATHML_TAG_INFOS};
+pub(crate) use svg::{SVG_GLOBAL_ATTRIBUTES, SVG_TAG_INFOS};
+pub(crate) use tags::{BOOLEAN_ATTRIBUTES, GLOBAL_ATTRIBUTES, TAG_INFOS};
+
+
+/// The namespaces elements can be in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Namespace {
+    Html,
+    Svg,
+    MathMl,
+}
+
+impl Namespace {
+    /// All namespaces other than HTML. These can only be entered via their
+    /// root element (see `root`).
+    const FOREIGN: &'static [Namespace] = &[Namespace::Svg, Namespace::MathMl];
+
+    /// The namespace URI, as passed to `create_element_ns`.
+    pub(crate) fn uri(self) -> &'static str {
+        match self {
+            Namespace::Html => "http://www.w3.org/1999/xhtml",
+            Namespace::Svg => "http://www.w3.org/2000/svg",
+            Namespace::MathMl => "http://www.w3.org/1998/Math/MathML",
+        }
+    }
+
+    /// The name for error messages.
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Namespace::Html => "HTML",
+            Namespace::Svg => "SVG",
+            Namespace::MathMl => "MathML",
+        }
+    }
+
+    /// The element through which HTML content can contain elements of this
+    /// namespace.
+    fn root(self) -> &'static str {
+        match self {
+            Namespace::Html => "html",
+            Namespace::Svg => "svg",
+            Namespace::MathMl => "math",
+        }
+    }
+
+    fn tags(self) -> &'static [TagInfo] {
+        match self {
+            Namespace::Html => TAG_INFOS,
+            Namespace::Svg => SVG_TAG_INFOS,
+            Namespace::MathMl => MATHML_TAG_INFOS,
+        }
+    }
 
+    fn global_attributes(self) -> &'static [&'static str] {
+        match self {
+            Namespace::Html => GLOBAL_ATTRIBUTES,
+            Namespace::Svg => SVG_GLOBAL_ATTRIBUTES,
+            Namespace::MathMl => MATHML_GLOBAL_ATTRIBUTES,
+        }
+    }
+}
 
-/// Information about a specific tag.
-#[derive(Debug)]
+/// Information about a specific tag. The infos of all standard tags are
+/// constants. Only custom elements (see `custom`) own their name and
+/// attributes.
+#[derive(Debug, Clone)]
 pub(crate) struct TagInfo {
     /// The name of the tag. E.g. `a`, `br` or `img`.
-    pub(crate) name: &'static str,
+    pub(crate) name: Cow<'static, str>,
 
     /// The name of the type in `web_sys`. This closely correlates with the
     /// "interface" in the HTML standard (only the casing is different).
-    pub(crate) ty: &'static str,
+    pub(crate) ty: Cow<'static, str>,
+
+    /// The namespace the element is created in.
+    pub(crate) namespace: Namespace,
+
+    /// Whether all attributes are allowed. That's only the case for custom
+    /// elements that are not declared in the registry.
+    pub(crate) allows_any_attribute: bool,
 
     /// What content models the tag belongs to.
     pub(crate) categories: &'static [ContentModel],
@@ -26,12 +135,26 @@ pub(crate) struct TagInfo {
 
     /// What attributes are allowed on this tag. This only lists non-global
     /// attributes. Every tag allows global attributes.
-    pub(crate) attributes: &'static [&'static str],
+    pub(crate) attributes: Cow<'static, [Cow<'static, str>]>,
 }
 
 impl TagInfo {
-    /// Returns
-    pub(crate) fn from_name(name: &Ident) -> Result<&'static Self, Error> {
+    /// Returns the tag with the given name. `namespace` is the namespace of
+    /// the parent element, or `None` if it is unknown (e.g. for the root of
+    /// the markup). In the latter case, all namespaces are searched, starting
+    /// with HTML.
+    pub(crate) fn from_name(
+        name: &Name,
+        namespace: Option<Namespace>,
+    ) -> Result<Cow<'static, Self>, Error> {
+        // Names with hyphens are custom elements, which only exist in HTML.
+        if name.as_ident().is_none() {
+            return match namespace {
+                None | Some(Namespace::Html) => custom::lookup(name),
+                Some(namespace) => Err(Error::unknown_tag(name, namespace)),
+            };
+        }
+
         // Yes, we actually do check if an array is sorted before doing a
         // binary search. BUT, we only do it once and only in debug mode. I
         // still like to have this assert here as otherwise, a binary search on
@@ -42,38 +165,286 @@ impl TagInfo {
 
             static CHECK_SORTED: Once = Once::new();
             CHECK_SORTED.call_once(|| {
-                let is_sorted = TAG_INFOS.iter()
-                    .zip(&TAG_INFOS[1..])
-                    .all(|(a, b)| a.name <= b.name);
+                for tags in &[TAG_INFOS, SVG_TAG_INFOS, MATHML_TAG_INFOS] {
+                    let is_sorted = tags.iter()
+                        .zip(&tags[1..])
+                        .all(|(a, b)| a.name <= b.name);
 
-                if !is_sorted {
-                    panic!("TAG_INFOS array is not sorted! This is a bug!");
+                    if !is_sorted {
+                        panic!("tag info array is not sorted! This is a bug!");
+                    }
                 }
             })
         }
 
-        TAG_INFOS
-            .binary_search_by_key(&name.to_string().as_str(), |info| info.name)
-            .map(|pos| &TAG_INFOS[pos])
-            .map_err(|_| Error::unknown_tag(name))
+        let name_string = name.to_string();
+        let find = |namespace: Namespace| {
+            let tags = namespace.tags();
+            tags.binary_search_by_key(&name_string.as_str(), |info| &*info.name)
+                .ok()
+                .map(|pos| Cow::Borrowed(&tags[pos]))
+        };
+
+        let info = match namespace {
+            // Inside HTML, foreign namespaces can be entered via their root
+            // element, e.g. `<svg>`.
+            Some(Namespace::Html) => find(Namespace::Html).or_else(|| {
+                Namespace::FOREIGN.iter()
+                    .filter(|ns| ns.root() == name_string)
+                    .find_map(|&ns| find(ns))
+            }),
+            Some(namespace) => find(namespace),
+            None => find(Namespace::Html)
+                .or_else(|| Namespace::FOREIGN.iter().find_map(|&ns| find(ns))),
+        };
+
+        info.ok_or_else(|| Error::unknown_tag(name, namespace.unwrap_or(Namespace::Html)))
+    }
+
+    /// Returns whether this is a void element, i.e. one that never has any
+    /// children and that has no end tag (like `<br>`).
+    pub(crate) fn is_void(&self) -> bool {
+        // In the HTML standard, the only non-void element that doesn't allow
+        // children is `<iframe>`.
+        self.namespace == Namespace::Html
+            && self.children.is_empty()
+            && self.name != "iframe"
     }
 
-    pub(crate) fn type_ident(&self) -> Ident {
-        Ident::new(self.ty, Span::call_site())
+    /// Returns whether the content of this element is raw text and not
+    /// markup (`<script>` and `<style>`).
+    pub(crate) fn has_raw_text(&self) -> bool {
+        self.namespace == Namespace::Html && (self.name == "script" || self.name == "style")
     }
 
-    pub(crate) fn check_attribute(&self, attr: &Ident) -> Result<(), Error> {
+    /// Returns whether a shadow root can be attached to this element. That's
+    /// only allowed for custom elements and the elements listed in the DOM
+    /// standard.
+    pub(crate) fn can_host_shadow(&self) -> bool {
+        const HOSTS: &[&str] = &[
+            "article", "aside", "blockquote", "body", "div", "footer", "h1", "h2", "h3", "h4",
+            "h5", "h6", "header", "main", "nav", "p", "section", "span",
+        ];
+
+        self.namespace == Namespace::Html
+            && (self.name.contains('-') || HOSTS.contains(&&*self.name))
+    }
+
+    /// Returns the path of the type of this element. Types without path are
+    /// in `web_sys`.
+    pub(crate) fn type_path(&self) -> TokenStream {
+        if self.ty.contains("::") {
+            // Only custom elements from the registry have paths, and those
+            // were tokens before.
+            self.ty.parse().expect("invalid type path in tag info")
+        } else {
+            let ty = Ident::new(&self.ty, Span::call_site());
+            quote! { ::web_sys::#ty }
+        }
+    }
+
+    pub(crate) fn check_attribute(&self, attr: &Name) -> Result<(), Error> {
         let s = &*attr.to_string();
-        if !self.attributes.contains(&s) && !GLOBAL_ATTRIBUTES.contains(&s) {
-            Err(Error::invalid_attr(attr, self.name))
+
+        // Custom data attributes are allowed everywhere. `aria-*` attributes
+        // are allowed everywhere, too, but they have to exist.
+        if s.starts_with("data-") || self.allows_any_attribute {
+            Ok(())
+        } else if s.starts_with("aria-") {
+            if ARIA_ATTRIBUTES.contains(&s) {
+                Ok(())
+            } else {
+                Err(Error::unknown_aria_attr(attr))
+            }
+        } else if !self.has_attribute(s)
+            && !self.namespace.global_attributes().contains(&s)
+        {
+            Err(Error::invalid_attr(attr, self))
         } else {
             Ok(())
         }
     }
+
+    /// Returns whether `attr` is one of the non-global attributes of this tag.
+    fn has_attribute(&self, attr: &str) -> bool {
+        self.attributes.iter().any(|a| a == attr)
+    }
+
+    /// Returns whether `attr` is a boolean attribute (like `disabled`) on this
+    /// tag, i.e. whether `false` removes it instead of setting it to
+    /// `"false"`. Custom elements get all boolean attributes.
+    pub(crate) fn is_boolean_attribute(&self, attr: &str) -> bool {
+        self.namespace == Namespace::Html
+            && BOOLEAN_ATTRIBUTES.contains(&attr)
+            && (self.allows_any_attribute
+                || self.has_attribute(attr)
+                || GLOBAL_ATTRIBUTES.contains(&attr))
+    }
+
+    /// Returns all boolean attributes of this tag (see
+    /// `is_boolean_attribute`).
+    pub(crate) fn boolean_attributes(&self) -> Vec<&'static str> {
+        BOOLEAN_ATTRIBUTES.iter().copied().filter(|attr| self.is_boolean_attribute(attr)).collect()
+    }
+
+    /// Returns the children allowed inside this tag, given the children
+    /// allowed at the position of this tag (`parent`). The latter is only
+    /// relevant for transparent tags.
+    pub(crate) fn allowed_children(&self, parent: &AllowedChildren) -> AllowedChildren {
+        // `foreignObject` is the way back from SVG to HTML.
+        let namespace = match (self.namespace, &*self.name) {
+            (Namespace::Svg, "foreignObject") => Namespace::Html,
+            (namespace, _) => namespace,
+        };
+
+        // The content of `<template>` is a separate fragment that can contain
+        // anything.
+        if self.children.iter().any(|c| matches!(c, Child::Any)) {
+            return AllowedChildren::Any;
+        }
+
+        let own = self.children.iter()
+            .filter(|c| !matches!(c, Child::Transparent))
+            .cloned();
+
+        if !self.children.iter().any(|c| matches!(c, Child::Transparent)) {
+            return AllowedChildren::Only {
+                parent: self.clone(),
+                namespace,
+                children: own.collect(),
+            };
+        }
+
+        // Transparent tags allow everything that is allowed in their parent.
+        match parent {
+            AllowedChildren::Any => AllowedChildren::Any,
+            AllowedChildren::Only { children, .. } => AllowedChildren::Only {
+                parent: self.clone(),
+                namespace,
+                children: own.chain(children.iter().cloned()).collect(),
+            },
+        }
+    }
 }
 
-/// The main content models of HTML.
-#[derive(Debug, Clone, Copy)]
+/// Checks that `property` (the name of a `style:` directive) is a known CSS
+/// property.
+pub(crate) fn check_css_property(property: &Name) -> Result<(), Error> {
+    if CSS_PROPERTIES.contains(&&*property.to_string()) {
+        Ok(())
+    } else {
+        Err(Error::unknown_css_property(property))
+    }
+}
+
+/// Returns the name of the `web_sys` type of the event with the given name
+/// (e.g. `MouseEvent` for `click`). Unknown events get the type `Event`.
+pub(crate) fn event_type_ident(event: &str) -> Ident {
+    let ty = EVENT_TYPES.iter()
+        .find(|(name, _)| *name == event)
+        .map(|(_, ty)| *ty)
+        .unwrap_or("Event");
+
+    Ident::new(ty, Span::call_site())
+}
+
+/// The set of children that are allowed at a specific position in the markup.
+#[derive(Debug, Clone)]
+pub(crate) enum AllowedChildren {
+    /// Everything is allowed. This is the case for the root of the markup and
+    /// for children of components, as we don't know where they end up.
+    Any,
+
+    /// Only children matching one of the given rules are allowed. `children`
+    /// never contains `Child::Transparent`, as that is already resolved.
+    /// Child tags are looked up in `namespace`.
+    Only {
+        parent: TagInfo,
+        namespace: Namespace,
+        children: Vec<Child>,
+    },
+}
+
+impl AllowedChildren {
+    /// The namespace in which child tags are looked up, or `None` if that's
+    /// unknown.
+    pub(crate) fn namespace(&self) -> Option<Namespace> {
+        match self {
+            AllowedChildren::Any => None,
+            AllowedChildren::Only { namespace, .. } => Some(*namespace),
+        }
+    }
+
+    /// Checks that the tag `info` (with the name `name`) is allowed here.
+    pub(crate) fn check_tag(&self, info: &TagInfo, name: &Name) -> Result<(), Error> {
+        match self {
+            AllowedChildren::Any => Ok(()),
+            AllowedChildren::Only { parent, children, .. } => {
+                let is_allowed = children.iter().any(|child| match child {
+                    Child::Any | Child::Transparent | Child::Text => false,
+                    Child::Model(model) => info.categories.contains(model),
+                    Child::Tag(tag) => *tag == info.name,
+                });
+
+                if is_allowed {
+                    Ok(())
+                } else {
+                    let what = format!("'<{}>'", info.name);
+                    Err(Error::invalid_child(name.span(), &what, parent, &describe(children)))
+                }
+            }
+        }
+    }
+
+    /// Checks that text is allowed here, if `content` is a string literal.
+    /// All other expressions are not checked as we don't know what node they
+    /// will result in. Whitespace is always allowed.
+    pub(crate) fn check_content(&self, content: &TokenTree) -> Result<(), Error> {
+        let (parent, children) = match self {
+            AllowedChildren::Any => return Ok(()),
+            AllowedChildren::Only { parent, children, .. } => (parent, children),
+        };
+
+        let text = match syn::parse2::<syn::LitStr>(content.clone().into()) {
+            Ok(lit) => lit.value(),
+            Err(_) => return Ok(()),
+        };
+
+        // Text is phrasing content which in turn is flow content.
+        let is_allowed = text.trim().is_empty() || children.iter().any(|child| match child {
+            Child::Text | Child::Model(ContentModel::Phrasing) | Child::Model(ContentModel::Flow)
+                => true,
+            _ => false,
+        });
+
+        if is_allowed {
+            Ok(())
+        } else {
+            Err(Error::invalid_child(content.span(), "text", parent, &describe(children)))
+        }
+    }
+}
+
+/// Returns a human readable list of the given allowed children.
+fn describe(children: &[Child]) -> String {
+    if children.is_empty() {
+        return "nothing".into();
+    }
+
+    children.iter()
+        .map(|child| match child {
+            Child::Any | Child::Transparent => unreachable!("already resolved"),
+            Child::Text => "text".to_string(),
+            Child::Model(model) => format!("{} content", model.name()),
+            Child::Tag(tag) => format!("'<{}>'", tag),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// The main content models of HTML, the element categories of SVG (which are
+/// used like content models) and "MathML content".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum ContentModel {
     Metadata,
     Flow,
@@ -82,11 +453,46 @@ pub(crate) enum ContentModel {
     Phrasing,
     Embedded,
     Interactive,
+
+    SvgAnimation,
+    SvgDescriptive,
+    SvgFilterPrimitive,
+    SvgPaintServer,
+    SvgShape,
+    SvgStructural,
+
+    MathMl,
+}
+
+impl ContentModel {
+    /// The name as used in the standards, e.g. "flow" for `Flow`.
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            ContentModel::Metadata => "metadata",
+            ContentModel::Flow => "flow",
+            ContentModel::Sectioning => "sectioning",
+            ContentModel::Heading => "heading",
+            ContentModel::Phrasing => "phrasing",
+            ContentModel::Embedded => "embedded",
+            ContentModel::Interactive => "interactive",
+            ContentModel::SvgAnimation => "SVG animation",
+            ContentModel::SvgDescriptive => "SVG descriptive",
+            ContentModel::SvgFilterPrimitive => "SVG filter primitive",
+            ContentModel::SvgPaintServer => "SVG paint server",
+            ContentModel::SvgShape => "SVG shape",
+            ContentModel::SvgStructural => "SVG structural",
+            ContentModel::MathMl => "MathML",
+        }
+    }
 }
 
 /// Specifies what kind of child is allowed for another tag.
 #[derive(Debug, Clone, Copy)]
 pub(crate) enum Child {
+    /// Everything is allowed. This is only used for `<template>`, whose
+    /// content doesn't end up in the template element itself.
+    Any,
+
     /// The same children are allowed in this tag as in the parent tag.
     Transparent,
 
@@ -0,0 +1,402 @@
+//! This module holds data about the elements defined in the SVG standard.
+
+use super::{ContentModel::*, Child::*, Namespace::Svg, TagInfo};
+
+/// Attributes that are allowed on all SVG elements: the core attributes, the
+/// styling attributes, the conditional processing attributes and all
+/// presentation attributes of SVG 2:
+///
+///     https://www.w3.org/TR/SVG2/attindex.html
+///
+/// Additionally, `tabindex`, `autofocus`, `nonce` and `role` are added, as
+/// SVG elements share those with HTML.
+pub(crate) const SVG_GLOBAL_ATTRIBUTES: &[&str] = &[
+    "alignment-baseline",
+    "autofocus",
+    "baseline-shift",
+    "class",
+    "clip",
+    "clip-path",
+    "clip-rule",
+    "color",
+    "color-interpolation",
+    "color-interpolation-filters",
+    "cursor",
+    "direction",
+    "display",
+    "dominant-baseline",
+    "fill",
+    "fill-opacity",
+    "fill-rule",
+    "filter",
+    "flood-color",
+    "flood-opacity",
+    "font-family",
+    "font-size",
+    "font-size-adjust",
+    "font-stretch",
+    "font-style",
+    "font-variant",
+    "font-weight",
+    "id",
+    "image-rendering",
+    "lang",
+    "letter-spacing",
+    "lighting-color",
+    "marker-end",
+    "marker-mid",
+    "marker-start",
+    "mask",
+    "nonce",
+    "opacity",
+    "overflow",
+    "paint-order",
+    "pointer-events",
+    "requiredExtensions",
+    "role",
+    "shape-rendering",
+    "stop-color",
+    "stop-opacity",
+    "stroke",
+    "stroke-dasharray",
+    "stroke-dashoffset",
+    "stroke-linecap",
+    "stroke-linejoin",
+    "stroke-miterlimit",
+    "stroke-opacity",
+    "stroke-width",
+    "style",
+    "systemLanguage",
+    "tabindex",
+    "text-anchor",
+    "text-decoration",
+    "text-rendering",
+    "transform",
+    "transform-origin",
+    "unicode-bidi",
+    "vector-effect",
+    "visibility",
+    "word-spacing",
+    "writing-mode",
+];
+
+// This data is based on the element index of the SVG 2 standard:
+//
+//     https://www.w3.org/TR/SVG2/eltindex.html
+//
+// Like the HTML data, it is simplified:
+//
+// - Only commonly used elements are listed. Deprecated elements and most
+//   filter primitives are missing.
+// - The content models are simplified, e.g. `<a>` allows everything that
+//   containers and text content elements allow.
+// - `foreignObject` allows HTML flow content. That is handled specially when
+//   looking up the children.
+def_tags!(SVG_TAG_INFOS, Svg; [
+    // Syntax:
+    // tag: Type => { [categories...], [children...], [attributes...] }
+    a: SvgaElement => {
+        [],
+        [
+            Model(SvgAnimation), Model(SvgDescriptive), Model(SvgPaintServer), Model(SvgShape),
+            Model(SvgStructural), Tag("a"), Tag("clipPath"), Tag("filter"), Tag("foreignObject"),
+            Tag("image"), Tag("marker"), Tag("mask"), Tag("script"), Tag("style"), Tag("switch"),
+            Tag("text"), Tag("view"), Text, Tag("tspan"),
+        ],
+        ["href", "target", "download", "ping", "rel", "hreflang", "type", "referrerpolicy"],
+    },
+    animate: SvgAnimateElement => {
+        [SvgAnimation],
+        [Model(SvgDescriptive)],
+        [
+            "attributeName", "begin", "dur", "end", "min", "max", "restart", "repeatCount",
+            "repeatDur", "fill", "href", "calcMode", "values", "keyTimes", "keySplines", "from",
+            "to", "by", "additive", "accumulate",
+        ],
+    },
+    animateMotion: SvgAnimateMotionElement => {
+        [SvgAnimation],
+        [Model(SvgDescriptive), Tag("mpath")],
+        [
+            "attributeName", "begin", "dur", "end", "min", "max", "restart", "repeatCount",
+            "repeatDur", "fill", "href", "calcMode", "values", "keyTimes", "keySplines", "from",
+            "to", "by", "additive", "accumulate", "path", "keyPoints", "rotate", "origin",
+        ],
+    },
+    animateTransform: SvgAnimateTransformElement => {
+        [SvgAnimation],
+        [Model(SvgDescriptive)],
+        [
+            "attributeName", "begin", "dur", "end", "min", "max", "restart", "repeatCount",
+            "repeatDur", "fill", "href", "calcMode", "values", "keyTimes", "keySplines", "from",
+            "to", "by", "additive", "accumulate", "type",
+        ],
+    },
+    circle: SvgCircleElement => {
+        [SvgShape],
+        [Model(SvgAnimation), Model(SvgDescriptive)],
+        ["cx", "cy", "r", "pathLength"],
+    },
+    clipPath: SvgClipPathElement => {
+        [],
+        [Model(SvgAnimation), Model(SvgDescriptive), Model(SvgShape), Tag("text"), Tag("use")],
+        ["clipPathUnits"],
+    },
+    defs: SvgDefsElement => {
+        [SvgStructural],
+        [
+            Model(SvgAnimation), Model(SvgDescriptive), Model(SvgPaintServer), Model(SvgShape),
+            Model(SvgStructural), Tag("a"), Tag("clipPath"), Tag("filter"), Tag("foreignObject"),
+            Tag("image"), Tag("marker"), Tag("mask"), Tag("script"), Tag("style"), Tag("switch"),
+            Tag("text"), Tag("view"),
+        ],
+        [],
+    },
+    desc: SvgDescElement => {
+        [SvgDescriptive],
+        [Text],
+        [],
+    },
+    ellipse: SvgEllipseElement => {
+        [SvgShape],
+        [Model(SvgAnimation), Model(SvgDescriptive)],
+        ["cx", "cy", "rx", "ry", "pathLength"],
+    },
+    feBlend: SvgfeBlendElement => {
+        [SvgFilterPrimitive],
+        [Tag("animate"), Tag("set")],
+        ["x", "y", "width", "height", "result", "in", "in2", "mode"],
+    },
+    feColorMatrix: SvgfeColorMatrixElement => {
+        [SvgFilterPrimitive],
+        [Tag("animate"), Tag("set")],
+        ["x", "y", "width", "height", "result", "in", "type", "values"],
+    },
+    feComposite: SvgfeCompositeElement => {
+        [SvgFilterPrimitive],
+        [Tag("animate"), Tag("set")],
+        ["x", "y", "width", "height", "result", "in", "in2", "operator", "k1", "k2", "k3", "k4"],
+    },
+    feDropShadow: SvgfeDropShadowElement => {
+        [SvgFilterPrimitive],
+        [Tag("animate"), Tag("set")],
+        ["x", "y", "width", "height", "result", "in", "dx", "dy", "stdDeviation"],
+    },
+    feFlood: SvgfeFloodElement => {
+        [SvgFilterPrimitive],
+        [Tag("animate"), Tag("set")],
+        ["x", "y", "width", "height", "result"],
+    },
+    feGaussianBlur: SvgfeGaussianBlurElement => {
+        [SvgFilterPrimitive],
+        [Tag("animate"), Tag("set")],
+        ["x", "y", "width", "height", "result", "in", "stdDeviation", "edgeMode"],
+    },
+    feMerge: SvgfeMergeElement => {
+        [SvgFilterPrimitive],
+        [Tag("feMergeNode")],
+        ["x", "y", "width", "height", "result"],
+    },
+    feMergeNode: SvgfeMergeNodeElement => {
+        [],
+        [Tag("animate"), Tag("set")],
+        ["in"],
+    },
+    feOffset: SvgfeOffsetElement => {
+        [SvgFilterPrimitive],
+        [Tag("animate"), Tag("set")],
+        ["x", "y", "width", "height", "result", "in", "dx", "dy"],
+    },
+    filter: SvgFilterElement => {
+        [],
+        [Model(SvgDescriptive), Model(SvgFilterPrimitive), Tag("animate"), Tag("set")],
+        ["x", "y", "width", "height", "filterUnits", "primitiveUnits"],
+    },
+    foreignObject: SvgForeignObjectElement => {
+        [],
+        [Model(Flow)],
+        ["x", "y", "width", "height"],
+    },
+    g: SvggElement => {
+        [SvgStructural],
+        [
+            Model(SvgAnimation), Model(SvgDescriptive), Model(SvgPaintServer), Model(SvgShape),
+            Model(SvgStructural), Tag("a"), Tag("clipPath"), Tag("filter"), Tag("foreignObject"),
+            Tag("image"), Tag("marker"), Tag("mask"), Tag("script"), Tag("style"), Tag("switch"),
+            Tag("text"), Tag("view"),
+        ],
+        [],
+    },
+    image: SvgImageElement => {
+        [],
+        [Model(SvgAnimation), Model(SvgDescriptive)],
+        ["href", "x", "y", "width", "height", "preserveAspectRatio", "crossorigin", "decoding"],
+    },
+    line: SvgLineElement => {
+        [SvgShape],
+        [Model(SvgAnimation), Model(SvgDescriptive)],
+        ["x1", "y1", "x2", "y2", "pathLength"],
+    },
+    linearGradient: SvgLinearGradientElement => {
+        [SvgPaintServer],
+        [Model(SvgDescriptive), Tag("animate"), Tag("animateTransform"), Tag("set"), Tag("stop")],
+        ["x1", "y1", "x2", "y2", "gradientUnits", "gradientTransform", "spreadMethod", "href"],
+    },
+    marker: SvgMarkerElement => {
+        [],
+        [
+            Model(SvgAnimation), Model(SvgDescriptive), Model(SvgPaintServer), Model(SvgShape),
+            Model(SvgStructural), Tag("a"), Tag("clipPath"), Tag("filter"), Tag("foreignObject"),
+            Tag("image"), Tag("marker"), Tag("mask"), Tag("script"), Tag("style"), Tag("switch"),
+            Tag("text"), Tag("view"),
+        ],
+        [
+            "viewBox", "preserveAspectRatio", "refX", "refY", "markerUnits", "markerWidth",
+            "markerHeight", "orient",
+        ],
+    },
+    mask: SvgMaskElement => {
+        [],
+        [
+            Model(SvgAnimation), Model(SvgDescriptive), Model(SvgPaintServer), Model(SvgShape),
+            Model(SvgStructural), Tag("a"), Tag("clipPath"), Tag("filter"), Tag("foreignObject"),
+            Tag("image"), Tag("marker"), Tag("mask"), Tag("script"), Tag("style"), Tag("switch"),
+            Tag("text"), Tag("view"),
+        ],
+        ["x", "y", "width", "height", "maskUnits", "maskContentUnits"],
+    },
+    metadata: SvgMetadataElement => {
+        [SvgDescriptive],
+        [Text],
+        [],
+    },
+    mpath: SvgmPathElement => {
+        [],
+        [Model(SvgDescriptive)],
+        ["href"],
+    },
+    path: SvgPathElement => {
+        [SvgShape],
+        [Model(SvgAnimation), Model(SvgDescriptive)],
+        ["d", "pathLength"],
+    },
+    pattern: SvgPatternElement => {
+        [SvgPaintServer],
+        [
+            Model(SvgAnimation), Model(SvgDescriptive), Model(SvgPaintServer), Model(SvgShape),
+            Model(SvgStructural), Tag("a"), Tag("clipPath"), Tag("filter"), Tag("foreignObject"),
+            Tag("image"), Tag("marker"), Tag("mask"), Tag("script"), Tag("style"), Tag("switch"),
+            Tag("text"), Tag("view"),
+        ],
+        [
+            "viewBox", "preserveAspectRatio", "x", "y", "width", "height", "patternUnits",
+            "patternContentUnits", "patternTransform", "href",
+        ],
+    },
+    polygon: SvgPolygonElement => {
+        [SvgShape],
+        [Model(SvgAnimation), Model(SvgDescriptive)],
+        ["points", "pathLength"],
+    },
+    polyline: SvgPolylineElement => {
+        [SvgShape],
+        [Model(SvgAnimation), Model(SvgDescriptive)],
+        ["points", "pathLength"],
+    },
+    radialGradient: SvgRadialGradientElement => {
+        [SvgPaintServer],
+        [Model(SvgDescriptive), Tag("animate"), Tag("animateTransform"), Tag("set"), Tag("stop")],
+        [
+            "cx", "cy", "r", "fx", "fy", "fr", "gradientUnits", "gradientTransform", "spreadMethod",
+            "href",
+        ],
+    },
+    rect: SvgRectElement => {
+        [SvgShape],
+        [Model(SvgAnimation), Model(SvgDescriptive)],
+        ["x", "y", "width", "height", "rx", "ry", "pathLength"],
+    },
+    script: SvgScriptElement => {
+        [],
+        [Text],
+        ["type", "href", "crossorigin"],
+    },
+    set: SvgSetElement => {
+        [SvgAnimation],
+        [Model(SvgDescriptive)],
+        [
+            "attributeName", "begin", "dur", "end", "min", "max", "restart", "repeatCount",
+            "repeatDur", "fill", "href", "to",
+        ],
+    },
+    stop: SvgStopElement => {
+        [],
+        [Tag("animate"), Tag("set")],
+        ["offset"],
+    },
+    style: SvgStyleElement => {
+        [],
+        [Text],
+        ["type", "media", "title"],
+    },
+    svg: SvgsvgElement => {
+        [Flow, Phrasing, Embedded, SvgStructural],
+        [
+            Model(SvgAnimation), Model(SvgDescriptive), Model(SvgPaintServer), Model(SvgShape),
+            Model(SvgStructural), Tag("a"), Tag("clipPath"), Tag("filter"), Tag("foreignObject"),
+            Tag("image"), Tag("marker"), Tag("mask"), Tag("script"), Tag("style"), Tag("switch"),
+            Tag("text"), Tag("view"),
+        ],
+        ["viewBox", "preserveAspectRatio", "x", "y", "width", "height", "xmlns"],
+    },
+    switch: SvgSwitchElement => {
+        [],
+        [
+            Model(SvgAnimation), Model(SvgDescriptive), Model(SvgPaintServer), Model(SvgShape),
+            Model(SvgStructural), Tag("a"), Tag("clipPath"), Tag("filter"), Tag("foreignObject"),
+            Tag("image"), Tag("marker"), Tag("mask"), Tag("script"), Tag("style"), Tag("switch"),
+            Tag("text"), Tag("view"),
+        ],
+        [],
+    },
+    symbol: SvgSymbolElement => {
+        [SvgStructural],
+        [
+            Model(SvgAnimation), Model(SvgDescriptive), Model(SvgPaintServer), Model(SvgShape),
+            Model(SvgStructural), Tag("a"), Tag("clipPath"), Tag("filter"), Tag("foreignObject"),
+            Tag("image"), Tag("marker"), Tag("mask"), Tag("script"), Tag("style"), Tag("switch"),
+            Tag("text"), Tag("view"),
+        ],
+        ["viewBox", "preserveAspectRatio", "x", "y", "width", "height", "refX", "refY"],
+    },
+    text: SvgTextElement => {
+        [],
+        [Text, Model(SvgAnimation), Model(SvgDescriptive), Tag("a"), Tag("textPath"), Tag("tspan")],
+        ["x", "y", "dx", "dy", "rotate", "textLength", "lengthAdjust"],
+    },
+    textPath: SvgTextPathElement => {
+        [],
+        [Text, Model(SvgAnimation), Model(SvgDescriptive), Tag("a"), Tag("tspan")],
+        ["href", "path", "startOffset", "method", "spacing", "side", "textLength", "lengthAdjust"],
+    },
+    title: SvgTitleElement => {
+        [SvgDescriptive],
+        [Text],
+        [],
+    },
+    tspan: SvgtSpanElement => {
+        [],
+        [Text, Model(SvgAnimation), Model(SvgDescriptive), Tag("a"), Tag("textPath"), Tag("tspan")],
+        ["x", "y", "dx", "dy", "rotate", "textLength", "lengthAdjust"],
+    },
+    use: SvgUseElement => {
+        [SvgStructural],
+        [Model(SvgAnimation), Model(SvgDescriptive)],
+        ["href", "x", "y", "width", "height"],
+    },
+    view: SvgViewElement => {
+        [],
+        [Model(SvgDescriptive)],
+        ["viewBox", "preserveAspectRatio"],
+    },
+]);
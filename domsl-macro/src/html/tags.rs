@@ -1,14 +1,16 @@
 //! This module holds data about the tags/elements defined in the HTML
 //! standard.
 
-use super::{ContentModel::*, Child::*, TagInfo};
+use super::{ContentModel::*, Child::*, Namespace::Html, TagInfo};
 
 /// All global HTML attributes as specified by the standard chapter 3.2.6.
 ///
 ///     https://html.spec.whatwg.org/#global-attributes
 ///
 /// Additionally, 'id' and 'class' are added, as those are (for some reason)
-/// not in that list of the HTML standard.
+/// not in that list of the HTML standard. 'role' is added, too, as it is a
+/// global attribute according to WAI-ARIA. `data-*` and `aria-*` attributes
+/// are handled separately.
 #[allow(dead_code)] // TODO
 pub(crate) const GLOBAL_ATTRIBUTES: &[&str] = &[
     "accesskey",
@@ -30,6 +32,9 @@ pub(crate) const GLOBAL_ATTRIBUTES: &[&str] = &[
     "itemtype",
     "lang",
     "nonce",
+    "part",
+    "role",
+    "slot",
     "spellcheck",
     "style",
     "tabindex",
@@ -37,27 +42,41 @@ pub(crate) const GLOBAL_ATTRIBUTES: &[&str] = &[
     "translate",
 ];
 
-// The HTML tag data is specified in a custom syntax so that we can generate
-// different structures out of it.
-macro_rules! def_tags {
-    ($const_name:ident; [ $(
-        $name:ident: $ty:ident => {
-            [ $($category:ident),* ],
-            [ $($child:expr),* ],
-            [ $($attribute:literal),* ],
-        },
-    )* ]) => {
-        pub(crate) const $const_name: &[TagInfo] = &[ $(
-            TagInfo {
-                name: stringify!($name),
-                ty: stringify!($ty),
-                categories: &[ $( $category ),* ],
-                children: &[ $( $child ),* ],
-                attributes: &[ $( $attribute ),* ],
-            },
-        )* ];
-    };
-}
+/// All boolean attributes of the HTML standard, i.e. attributes that are
+/// `true` if present (regardless of their value) and `false` if absent.
+///
+///     https://html.spec.whatwg.org/#attributes-3
+///
+/// A `bool` value removes these attributes if it's `false`. All other
+/// attributes (like `aria-pressed` or `draggable`) are set to `"true"` or
+/// `"false"`.
+pub(crate) const BOOLEAN_ATTRIBUTES: &[&str] = &[
+    "allowfullscreen",
+    "async",
+    "autofocus",
+    "autoplay",
+    "checked",
+    "controls",
+    "default",
+    "defer",
+    "disabled",
+    "formnovalidate",
+    "hidden",
+    "inert",
+    "ismap",
+    "itemscope",
+    "loop",
+    "multiple",
+    "muted",
+    "nomodule",
+    "novalidate",
+    "open",
+    "playsinline",
+    "readonly",
+    "required",
+    "reversed",
+    "selected",
+];
 
 // This data was generated from the table of HTML elements from the official
 // HTML standard (in the section "Index"):
@@ -67,13 +86,14 @@ macro_rules! def_tags {
 // However, HTML is messy and we can't capture all rules within this single
 // list. The table from the standard was simplified:
 //
-// - The `svg` tag, MathML elements and custom elements were ignored.
+// - The `svg` and `math` tags (which are in `svg.rs` and `mathml.rs`) and
+//   custom elements were ignored.
 // - Only the columns "element", "categories", "children", "attributes" and
 //   "Interface" were used.
 // - All asterisks (denoting special rules) were removed.
 // - For some tags, certain parts of the "categories" or "children" column were
 //   ignored (some of them are marked with "TODO").
-def_tags!(TAG_INFOS; [
+def_tags!(TAG_INFOS, Html; [
     // Syntax:
     // tag: Type => { [categories...], [children...], [attributes...] }
     a: HtmlAnchorElement => {
@@ -143,7 +163,7 @@ def_tags!(TAG_INFOS; [
             "onmessage", "onmessageerror", "onoffline", "ononline", "onpagehide", "onpageshow",
             "onpopstate", "onrejectionhandled", "onstorage", "onunhandledrejection", "onunload"],
     },
-    br: HtmlBRElement => {
+    br: HtmlBrElement => {
         [Flow, Phrasing],
         [],
         [],
@@ -314,7 +334,7 @@ def_tags!(TAG_INFOS; [
         [Tag("h1"), Tag("h2"), Tag("h3"), Tag("h4"), Tag("h5"), Tag("h6")],
         [],
     },
-    hr: HtmlHRElement => {
+    hr: HtmlHrElement => {
         [Flow],
         [],
         [],
@@ -369,7 +389,7 @@ def_tags!(TAG_INFOS; [
         [Model(Phrasing)],
         [],
     },
-    li: HtmlLIElement => {
+    li: HtmlLiElement => {
         [],
         [Model(Flow)],
         ["value"],
@@ -417,7 +437,7 @@ def_tags!(TAG_INFOS; [
     },
     noscript: HtmlElement => {
         [Metadata, Flow, Phrasing],
-        [], // TODO
+        [Transparent], // TODO: the actual rules are more complicated
         [],
     },
     object: HtmlObjectElement => {
@@ -502,7 +522,7 @@ def_tags!(TAG_INFOS; [
     },
     script: HtmlScriptElement => {
         [Metadata, Flow, Phrasing],
-        [], // TODO
+        [Text],
         ["src", "type", "async", "defer", "crossorigin", "integrity", "referrerpolicy"],
     },
     section: HtmlElement => {
@@ -577,7 +597,7 @@ def_tags!(TAG_INFOS; [
     },
     template: HtmlTemplateElement => {
         [Metadata, Flow, Phrasing],
-        [],
+        [Any],
         [],
     },
     textarea: HtmlTextAreaElement => {
@@ -1,10 +1,13 @@
-use proc_macro2::{Ident, Spacing, Span, TokenStream, TokenTree};
+use std::slice;
+
+use proc_macro2::{Delimiter, Ident, Spacing, Span, TokenStream, TokenTree};
 use quote::{quote, quote_spanned};
-use snax::{SnaxAttribute, SnaxItem, SnaxTag, SnaxSelfClosingTag};
+use syn::spanned::Spanned;
 
 use crate::{
     error::Error,
-    html::TagInfo,
+    html::{self, AllowedChildren, Namespace, TagInfo},
+    parse::{Arm, Attribute, For, If, Item, Match, Name, Shadow, Tag},
 };
 
 
@@ -42,186 +45,629 @@ pub(crate) fn parse_outer(input: TokenStream) -> Result<(Ident, TokenStream), Er
 }
 
 /// The main entry point to generate the output code.
-pub(crate) fn gen(root: &SnaxItem, document: &Ident) -> Result<TokenStream, Error> {
+pub(crate) fn gen(root: &Item, document: &Ident) -> Result<TokenStream, Error> {
+    // Generate code for the root item.
+    let gen_code = gen_item(&root, &AllowedChildren::Any)?;
+
     // We need to cast the outer most element appropriately.
-    let ty_cast = match &root {
-        SnaxItem::Tag(SnaxTag { name, ..})
-            | SnaxItem::SelfClosingTag(SnaxSelfClosingTag { name, .. }) =>
-        {
-            if starts_lowercase(name) {
-                let ty = TagInfo::from_name(&name)?.type_ident();
-                quote! { .dyn_into::<::web_sys::#ty>().unwrap() }
-            } else {
-                // Components already return the correct type
-                quote! {}
+    let gen_code = match &root {
+        Item::Tag(Tag { name, .. }) if starts_lowercase(name) => {
+            let ty = TagInfo::from_name(&name, None)?.type_path();
+            quote! {
+                ::domsl::Backend::cast_element::<#ty>(#DOCUMENT_IDENT, #gen_code)
             }
         }
 
-        // The expressions already evaluates to the correct type.
-        SnaxItem::Fragment(_) => quote! {},
-        SnaxItem::Content(_) => quote! {},
+        // Components already return the correct type and expressions already
+        // evaluate to the correct type.
+        _ => gen_code,
     };
 
-    // Generate code for the root item and put it all together.
-    let gen_code = gen_item(&root)?;
+    // Put it all together.
+    let track_registry = html::custom::track_registry();
     let out = quote! {{
-        use wasm_bindgen::{prelude::*, JsCast};
-        use web_sys::{Document};
+        #track_registry
         use domsl::{
+            backend::AsBackend,
             specialization_hack::{
                 AsStrKind, NodeKind, DisplayKind, IterDisplayKind, IterNodeKind, IterStrKind, Wrap,
             }
         };
 
-        let #DOCUMENT_IDENT: &Document = &*&#document;
+        let #DOCUMENT_IDENT = #document.domsl_as_backend();
 
-        #gen_code #ty_cast
+        #gen_code
     }};
 
     Ok(out)
 }
 
-/// Generates an expression that creates a `web_sys::Node` representing the
-/// given `item`.
-fn gen_item(item: &SnaxItem) -> Result<TokenStream, Error> {
+/// Generates an expression that creates a node (of the backend) representing
+/// the given `item`. `allowed` specifies what children are allowed at the
+/// position of `item`.
+fn gen_item(item: &Item, allowed: &AllowedChildren) -> Result<TokenStream, Error> {
     let tokens = match item {
-        SnaxItem::Tag(tag) => gen_tag(&tag.name, &tag.attributes, &tag.children)?,
-        SnaxItem::SelfClosingTag(tag) => gen_tag(&tag.name, &tag.attributes, &[])?,
-        SnaxItem::Fragment(fragment) => {
-            let add_children = add_children(&fragment.children)?;
+        Item::Tag(tag) => gen_tag(tag, allowed)?,
+        Item::Fragment(children) => gen_fragment(children, allowed)?,
+        Item::Content(tt) => {
+            allowed.check_content(tt)?;
 
-            quote! {{
-                let #NODE_IDENT = #DOCUMENT_IDENT.create_document_fragment();
-                #add_children
-                ::web_sys::Node::from(#NODE_IDENT)
-            }}
-        }
-        SnaxItem::Content(tt) => {
             quote! {{
                 let #TMP_IDENT = (#tt);
-                (&&&&&Wrap(&#TMP_IDENT)).domsl_into_node()
-                    .into_node(#TMP_IDENT, &#DOCUMENT_IDENT)
+                (&&&&&Wrap(&#TMP_IDENT, #DOCUMENT_IDENT)).domsl_into_node()
+                    .into_node(#TMP_IDENT, #DOCUMENT_IDENT)
             }}
         }
+        Item::If(If { condition, then_branch, else_branch }) => {
+            let then_branch = gen_branch(then_branch, allowed)?;
+            let else_branch = gen_branch(else_branch, allowed)?;
+
+            quote! {
+                if #condition { #then_branch } else { #else_branch }
+            }
+        }
+
+        Item::Match(Match { expr, arms }) => {
+            let arms = arms.iter().map(|Arm { pattern, body }| {
+                let body = gen_item(body, allowed)?;
+                Ok(quote! { #pattern => #body, })
+            }).collect::<Result<TokenStream, Error>>()?;
+
+            quote! {
+                match #expr { #arms }
+            }
+        }
+
+        // A loop on its own (e.g. as root) appends its items to a fragment.
+        Item::For(_) => gen_fragment(slice::from_ref(item), allowed)?,
+
+        // Shadow roots are handled by `gen_html_tag`, so they end up here
+        // only if they are not the child of an element.
+        Item::Shadow(shadow) => return Err(Error::misplaced_shadow(shadow)),
     };
 
     Ok(tokens)
 }
 
-fn gen_tag(
-    name: &Ident,
-    attributes: &[SnaxAttribute],
-    children: &[SnaxItem],
-) -> Result<TokenStream, Error> {
-    if starts_lowercase(name) {
-        gen_html_tag(name, attributes, children)
+/// Generates an expression creating a fragment with the given children.
+fn gen_fragment(children: &[Item], allowed: &AllowedChildren) -> Result<TokenStream, Error> {
+    // The children of fragments end up in the fragment's parent.
+    let add_children = add_children(children, allowed)?;
+
+    Ok(quote! {{
+        let #NODE_IDENT = ::domsl::Backend::create_fragment(#DOCUMENT_IDENT);
+        #add_children
+        #NODE_IDENT
+    }})
+}
+
+/// Generates an expression creating one node from the items of a branch
+/// (e.g. of an `if`). A single item is used directly, everything else is put
+/// into a fragment. An empty branch results in an empty fragment, which adds
+/// nothing to the parent.
+fn gen_branch(items: &[Item], allowed: &AllowedChildren) -> Result<TokenStream, Error> {
+    match items {
+        [item] => gen_item(item, allowed),
+        items => gen_fragment(items, allowed),
+    }
+}
+
+fn gen_tag(tag: &Tag, allowed: &AllowedChildren) -> Result<TokenStream, Error> {
+    if starts_lowercase(&tag.name) {
+        gen_html_tag(tag, allowed)
     } else {
-        gen_component(name, attributes, children)
+        gen_component(tag)
     }
 }
 
-fn gen_html_tag(
-    name: &Ident,
-    attributes: &[SnaxAttribute],
-    children: &[SnaxItem],
-) -> Result<TokenStream, Error> {
-    let info = TagInfo::from_name(&name)?;
-    let name_string = name.to_string();
-    let set_attrs = set_attributes(&attributes, &info)?;
-    let add_children = add_children(&children)?;
+fn gen_html_tag(tag: &Tag, allowed: &AllowedChildren) -> Result<TokenStream, Error> {
+    let info = &*TagInfo::from_name(&tag.name, allowed.namespace())?;
+    allowed.check_tag(info, &tag.name)?;
+
+    let name_string = tag.name.to_string();
+    let set_attrs = set_attributes(tag, &info)?;
+    let set_classes = if has_dynamic_classes(tag) {
+        let classes = gen_classes(tag);
+        quote! {
+            if let Some(#TMP_IDENT) = ::domsl::attribute::Classes::value(&#classes) {
+                ::domsl::Backend::set_attribute(#DOCUMENT_IDENT, &#NODE_IDENT, "class", #TMP_IDENT);
+            }
+        }
+    } else {
+        quote! {}
+    };
+    let set_styles = if has_dynamic_styles(tag) {
+        let styles = gen_styles(tag)?;
+        quote! {
+            if let Some(#TMP_IDENT) = ::domsl::attribute::Styles::value(&#styles) {
+                ::domsl::Backend::set_attribute(#DOCUMENT_IDENT, &#NODE_IDENT, "style", #TMP_IDENT);
+            }
+        }
+    } else {
+        quote! {}
+    };
+    let set_spread_attrs = set_spread_attributes(tag, info);
+    let allowed_children = info.allowed_children(allowed);
+    let add_light_children = tag.children.iter()
+        .filter(|child| !is_shadow(child))
+        .map(|child| add_child(child, &allowed_children))
+        .collect::<Result<TokenStream, Error>>()?;
+
+    // The shadow root can contain anything, as it's a separate tree.
+    let attach_shadow = match shadow_child(tag, info)? {
+        Some(Shadow { closed, children, .. }) => {
+            let mode = if *closed { quote! { Closed } } else { quote! { Open } };
+            let add_shadow_children = add_children(children, &AllowedChildren::Any)?;
+            quote! {{
+                let #NODE_IDENT = ::domsl::Backend::attach_shadow(
+                    #DOCUMENT_IDENT,
+                    &#NODE_IDENT,
+                    ::domsl::backend::ShadowMode::#mode,
+                );
+                #add_shadow_children
+            }}
+        }
+        None => quote! {},
+    };
+
+    let create = match info.namespace {
+        Namespace::Html => quote! {
+            ::domsl::Backend::create_element(#DOCUMENT_IDENT, #name_string)
+        },
+        namespace => {
+            let uri = namespace.uri();
+            quote! {
+                ::domsl::Backend::create_element_ns(#DOCUMENT_IDENT, #uri, #name_string)
+            }
+        }
+    };
 
     Ok(quote! {{
-        // This only fails if we pass in a name with incorrect characters, like
-        // spaces. We assure that this is not the case in `TagInfo::from_name`.
-        let #NODE_IDENT = #DOCUMENT_IDENT.create_element(#name_string).unwrap();
+        let #NODE_IDENT = #create;
         #set_attrs
-        #add_children
-        ::web_sys::Node::from(#NODE_IDENT)
+        #set_classes
+        #set_styles
+        #set_spread_attrs
+        #attach_shadow
+        #add_light_children
+        #NODE_IDENT
     }})
 }
 
-fn gen_component(
-    name: &Ident,
-    attributes: &[SnaxAttribute],
-    children: &[SnaxItem],
-) -> Result<TokenStream, Error> {
-    let component = {
-        let fields = attributes.iter().map(|attr| {
-            match attr {
-                SnaxAttribute::Simple { name, value } => quote! { #name: #value },
-            }
-        });
-
-        quote! {
-            #name { #( #fields ),* }
-        }
+/// Returns the `<#shadow>` child of `tag` (with the info `info`), if any.
+/// Returns an error if there is more than one or if the element can't have
+/// a shadow root.
+pub(crate) fn shadow_child<'a>(tag: &'a Tag, info: &TagInfo) -> Result<Option<&'a Shadow>, Error> {
+    let mut shadows = tag.children.iter().filter_map(|child| match child {
+        Item::Shadow(shadow) => Some(shadow),
+        _ => None,
+    });
+
+    let shadow = match shadows.next() {
+        Some(shadow) => shadow,
+        None => return Ok(None),
     };
 
+    if let Some(second) = shadows.next() {
+        return Err(Error::new(second.span, "an element can only have one shadow root"));
+    }
+
+    if !info.can_host_shadow() {
+        let msg = format!(
+            "a shadow root can't be attached to '<{}>' (only to custom elements, '<div>', \
+                '<span>', '<p>', headings and sectioning elements)",
+            info.name,
+        );
+        return Err(Error::new(shadow.span, &msg));
+    }
+
+    Ok(Some(shadow))
+}
+
+pub(crate) fn is_shadow(item: &Item) -> bool {
+    match item {
+        Item::Shadow(_) => true,
+        _ => false,
+    }
+}
+
+fn gen_component(tag: &Tag) -> Result<TokenStream, Error> {
+    let name = &tag.name;
+    let component = gen_component_struct(tag)?;
+
     let children_vec = {
-        let nodes = children.iter().map(|c| gen_item(c)).collect::<Result<Vec<_>, _>>()?;
+        // We don't know where the component puts its children, so we can't
+        // check anything here.
+        let nodes = tag.children.iter()
+            .map(|c| gen_item(c, &AllowedChildren::Any))
+            .collect::<Result<Vec<_>, _>>()?;
         quote! {
             vec![ #( #nodes ),* ]
         }
     };
 
     let render_call = quote_spanned!(name.span()=>
-        ::domsl::Component::render
+        ::domsl::backend::render_component
     );
 
     Ok(quote! {
-        ::web_sys::Node::from(
-            #render_call(
-                #component,
-                #DOCUMENT_IDENT,
-                #children_vec,
-            )
-        )
+        #render_call(#DOCUMENT_IDENT, #component, #children_vec)
     })
 }
 
-fn set_attributes(attrs: &[SnaxAttribute], info: &TagInfo) -> Result<TokenStream, Error> {
-    attrs.iter().map(|attr| {
-        match attr {
-            SnaxAttribute::Simple { name, value } => {
+/// Generates the expression creating the component struct from the
+/// attributes of `tag`. That's done through the builder generated by
+/// `#[derive(Props)]` or `#[component]` (see `props`), so that props can be
+/// omitted.
+pub(crate) fn gen_component_struct(tag: &Tag) -> Result<TokenStream, Error> {
+    if let Some(spread) = tag.spreads.first() {
+        let msg = "spread attributes (`{..attrs}`) can't be used on components";
+        return Err(Error::new(spread.span(), msg));
+    }
+
+    let name = match tag.name.as_ident() {
+        Some(name) => name,
+        None => {
+            let msg = "component names can't contain `-` (custom elements have to start \
+                with a lowercase letter)";
+            return Err(Error::new(tag.name.span(), msg));
+        }
+    };
+
+    // The builder is created from a closure with a pattern of the component
+    // type, so that its generic parameters can be inferred.
+    let mut builder = quote_spanned!(name.span()=>
+        ::domsl::props::builder(|#name { .. }| ())
+    );
+    for (i, attr) in tag.attributes.iter().enumerate() {
+        if let Some(namespace) = &attr.namespace {
+            let msg = "namespaced attributes (like `on:click`) can't be used on components";
+            return Err(Error::new(namespace.span(), msg));
+        }
+
+        let Attribute { name: prop, value, .. } = attr;
+        match prop.as_ident() {
+            Some(prop) if prop == "ref" => {
+                let msg = "`ref` can only be used on elements, not components";
+                return Err(Error::new(prop.span(), msg));
+            }
+            Some(prop) => {
+                let prop_string = prop.to_string();
+                let is_duplicate = tag.attributes[..i].iter()
+                    .any(|other| other.name.to_string() == prop_string);
+                if is_duplicate {
+                    let msg = format!("prop `{}` of component `{}` is given twice", prop, name);
+                    return Err(Error::new(prop.span(), &msg));
+                }
+
+                builder = gen_set_prop(prop, value, builder);
+            }
+            None => {
+                return Err(Error::new(prop.span(), "component properties can't contain `-`"));
+            }
+        }
+    }
+
+    // Spanned on the component name, so that errors about missing props
+    // point there.
+    Ok(quote_spanned! {name.span()=> #builder.__domsl_build() })
+}
+
+/// Generates the expression setting `prop` to `value` on `builder`, by
+/// calling the setter method of the builder. That's spanned on the prop, so
+/// that the error for unknown props points there.
+fn gen_set_prop(prop: &Ident, value: &TokenTree, builder: TokenStream) -> TokenStream {
+    quote_spanned! {prop.span()=>
+        {
+            #[allow(unused_braces)]
+            let builder = #builder.#prop(#value);
+            builder
+        }
+    }
+}
+
+fn set_attributes(tag: &Tag, info: &TagInfo) -> Result<TokenStream, Error> {
+    let dynamic_classes = has_dynamic_classes(tag);
+    let dynamic_styles = has_dynamic_styles(tag);
+    let attrs = tag.attributes.iter()
+        .filter(|attr| !(dynamic_classes && is_class(attr)))
+        .filter(|attr| !(dynamic_styles && is_style(attr)));
+    attrs.map(|attr| {
+        let Attribute { name, value, .. } = attr;
+        match &attr.namespace {
+            Some(namespace) if namespace == "on" => {
+                let event_name = name.to_string();
+                let event_ty = html::event_type_ident(&event_name);
+                let listener = quote_spanned!(value.span()=>
+                    ::domsl::Backend::add_event_listener::<::web_sys::#event_ty, _>
+                );
+
+                Ok(quote! {
+                    #listener(#DOCUMENT_IDENT, &#NODE_IDENT, #event_name, (#value));
+                })
+            }
+            Some(namespace) if namespace == "prop" => {
+                // The property is set via the typed setter from `web_sys`,
+                // so the compiler checks that it exists and that the value
+                // has the correct type. The setter comes before the value so
+                // that the value can be coerced (e.g. `&String` to `&str`).
+                let prop_name = name.to_string();
+                let ty = info.type_path();
+                let setter = Ident::new(&setter_name(name)?, name.span());
+                let set_property = quote_spanned!(value.span()=>
+                    ::domsl::Backend::set_property::<#ty, _>
+                );
+
+                Ok(quote! {
+                    #set_property(
+                        #DOCUMENT_IDENT,
+                        &#NODE_IDENT,
+                        #prop_name,
+                        #ty::#setter,
+                        (#value),
+                    );
+                })
+            }
+            Some(namespace) => Err(Error::unknown_namespace(namespace)),
+            None if name.to_string() == "ref" => {
+                // The braces are removed so that the ref is only borrowed.
+                let element_ref = match value {
+                    TokenTree::Group(g) if g.delimiter() == Delimiter::Brace => g.stream(),
+                    other => {
+                        let msg = "expected `{ }` block with a `domsl::ElementRef` as value \
+                            of `ref`";
+                        return Err(Error::new(other.span(), msg));
+                    }
+                };
+                let ty = info.type_path();
+                let set_ref = quote_spanned!(value.span()=> ::domsl::ElementRef::set);
+
+                Ok(quote! {
+                    #set_ref(
+                        &(#element_ref),
+                        ::domsl::Backend::cast_element::<#ty>(
+                            #DOCUMENT_IDENT,
+                            ::std::clone::Clone::clone(&#NODE_IDENT),
+                        ),
+                    );
+                })
+            }
+            None => {
                 info.check_attribute(name)?;
 
                 let name = name.to_string();
-                let value = match value {
+                match value {
                     // If the token tree is a string literal, we don't need to
-                    // call `to_string()`.
+                    // convert anything.
                     //
                     // TODO: make this check a bit more robust.
                     TokenTree::Literal(lit) if lit.to_string().starts_with("\"") => {
-                        quote ! { #lit }
+                        Ok(quote! {
+                            ::domsl::Backend::set_attribute(
+                                #DOCUMENT_IDENT,
+                                &#NODE_IDENT,
+                                #name,
+                                #lit,
+                            );
+                        })
                     }
+
+                    // Everything else goes through `AttributeValue`, which
+                    // might also decide to not set the attribute at all.
                     other => {
-                        quote_spanned!(other.span()=>
-                            &::std::string::ToString::to_string(&#other)
-                        )
+                        let attribute_value = attribute_value_fn(info, &name, other.span());
+
+                        Ok(quote! {{
+                            let mut #ATTR_BUF_IDENT = ::std::string::String::new();
+                            if let Some(#TMP_IDENT)
+                                = #attribute_value(&(#other), &mut #ATTR_BUF_IDENT)
+                            {
+                                ::domsl::Backend::set_attribute(
+                                    #DOCUMENT_IDENT,
+                                    &#NODE_IDENT,
+                                    #name,
+                                    #TMP_IDENT,
+                                );
+                            }
+                        }})
                     }
-                };
+                }
+            }
+        }
+    }).collect()
+}
+
+/// Returns the name of the `web_sys` setter of the DOM property `prop`, e.g.
+/// `set_selected_index` for `selectedIndex` (or `selected_index`).
+fn setter_name(prop: &Name) -> Result<String, Error> {
+    let prop = match prop.as_ident() {
+        Some(ident) => ident.to_string(),
+        None => return Err(Error::new(prop.span(), "property names can't contain `-`")),
+    };
+
+    // Runs of uppercase letters are one word, e.g. `innerHTML` -> `inner_html`.
+    let mut out = String::from("set_");
+    let mut prev_lowercase = false;
+    for c in prop.chars() {
+        if c.is_uppercase() && prev_lowercase {
+            out.push('_');
+        }
+        prev_lowercase = c.is_lowercase() || c.is_numeric();
+        out.extend(c.to_lowercase());
+    }
+
+    Ok(out)
+}
+
+/// Returns whether `attr` is the `class` attribute or a `class:` directive.
+pub(crate) fn is_class(attr: &Attribute) -> bool {
+    match &attr.namespace {
+        Some(namespace) => namespace == "class",
+        None => attr.name.to_string() == "class",
+    }
+}
 
+/// Returns whether the `class` attribute of `tag` has to be built at runtime
+/// with `gen_classes`. That's the case if there are `class:` directives or
+/// if the value of `class` is not a string literal.
+pub(crate) fn has_dynamic_classes(tag: &Tag) -> bool {
+    tag.attributes.iter().filter(|attr| is_class(attr)).any(|attr| {
+        attr.namespace.is_some() || syn::parse2::<syn::LitStr>(attr.value.clone().into()).is_err()
+    })
+}
+
+/// Generates an expression evaluating to a `domsl::attribute::Classes`
+/// containing all classes from `class` and `class:` directives of `tag`.
+pub(crate) fn gen_classes(tag: &Tag) -> TokenStream {
+    let add_classes = tag.attributes.iter().filter(|attr| is_class(attr)).map(|attr| {
+        let value = &attr.value;
+        match &attr.namespace {
+            Some(_) => {
+                let class = attr.name.to_string();
+                let toggle = quote_spanned!(value.span()=> ::domsl::attribute::Classes::toggle);
+                quote! {
+                    #toggle(&mut #CLASSES_IDENT, #class, (#value));
+                }
+            }
+            None => quote! {{
+                let #TMP_IDENT = (#value);
+                (&ClassWrap(&#TMP_IDENT)).domsl_class_kind()
+                    .add_to(#TMP_IDENT, &mut #CLASSES_IDENT);
+            }},
+        }
+    });
+
+    quote! {{
+        use domsl::specialization_hack::{ClassIterKind, ClassValueKind, ClassWrap};
+
+        let mut #CLASSES_IDENT = ::domsl::attribute::Classes::new();
+        #( #add_classes )*
+        #CLASSES_IDENT
+    }}
+}
+
+/// Returns whether `attr` is the `style` attribute or a `style:` directive.
+pub(crate) fn is_style(attr: &Attribute) -> bool {
+    match &attr.namespace {
+        Some(namespace) => namespace == "style",
+        None => attr.name.to_string() == "style",
+    }
+}
+
+/// Returns whether the `style` attribute of `tag` has to be built at runtime
+/// with `gen_styles`. That's the case if there are `style:` directives or if
+/// the value of `style` is not a string literal.
+pub(crate) fn has_dynamic_styles(tag: &Tag) -> bool {
+    tag.attributes.iter().filter(|attr| is_style(attr)).any(|attr| {
+        attr.namespace.is_some() || syn::parse2::<syn::LitStr>(attr.value.clone().into()).is_err()
+    })
+}
+
+/// Generates an expression evaluating to a `domsl::attribute::Styles`
+/// containing all declarations from `style` and `style:` directives of
+/// `tag`. Returns an error if a directive names an unknown CSS property.
+pub(crate) fn gen_styles(tag: &Tag) -> Result<TokenStream, Error> {
+    let add_styles = tag.attributes.iter().filter(|attr| is_style(attr)).map(|attr| {
+        let value = &attr.value;
+        match &attr.namespace {
+            Some(_) => {
+                html::check_css_property(&attr.name)?;
+                let property = attr.name.to_string();
+                let set = quote_spanned!(value.span()=> ::domsl::attribute::Styles::set);
                 Ok(quote! {
-                    // This only errors if 'name' contains illegal characters
-                    // which we check in `check_attribute_name`.
-                    #NODE_IDENT.set_attribute(#name, #value).unwrap();
+                    #set(&mut #STYLES_IDENT, #property, &(#value));
+                })
+            }
+            None => {
+                let add = quote_spanned!(value.span()=> ::domsl::attribute::Styles::add);
+                Ok(quote! {
+                    #add(&mut #STYLES_IDENT, &(#value));
                 })
             }
         }
-    }).collect()
+    }).collect::<Result<Vec<_>, Error>>()?;
+
+    Ok(quote! {{
+        let mut #STYLES_IDENT = ::domsl::attribute::Styles::new();
+        #( #add_styles )*
+        #STYLES_IDENT
+    }})
 }
 
-fn add_children(children: &[SnaxItem]) -> Result<TokenStream, Error> {
-    children.iter().map(|c| {
-        let child = gen_item(c)?;
-        Ok(quote! { #NODE_IDENT.append_child(&#child).unwrap(); })
+/// Returns the path of the `AttributeValue` method converting values of the
+/// attribute `name` of `info`: boolean attributes treat `bool`s differently.
+pub(crate) fn attribute_value_fn(info: &TagInfo, name: &str, span: Span) -> TokenStream {
+    if info.is_boolean_attribute(name) {
+        quote_spanned!(span=> ::domsl::AttributeValue::boolean_attribute_value)
+    } else {
+        quote_spanned!(span=> ::domsl::AttributeValue::attribute_value)
+    }
+}
+
+/// Generates the code setting the spread attributes (`{..attrs}`) of `tag`.
+fn set_spread_attributes(tag: &Tag, info: &TagInfo) -> TokenStream {
+    let explicit = explicit_attribute_names(tag);
+    let boolean = info.boolean_attributes();
+    tag.spreads.iter().map(|spread| {
+        let spread_fn = quote_spanned!(spread.span()=> ::domsl::attribute::spread);
+        quote! {
+            #spread_fn((#spread), &[ #( #explicit ),* ], &[ #( #boolean ),* ], |name, value| {
+                ::domsl::Backend::set_attribute(#DOCUMENT_IDENT, &#NODE_IDENT, name, value);
+            });
+        }
     }).collect()
 }
 
+/// Returns the names of all normal attributes of `tag`, which take precedence
+/// over spread attributes.
+pub(crate) fn explicit_attribute_names(tag: &Tag) -> Vec<String> {
+    let mut names = tag.attributes.iter()
+        .filter_map(|attr| match &attr.namespace {
+            None => Some(attr.name.to_string()),
+            Some(_) if is_class(attr) => Some("class".to_string()),
+            Some(_) if is_style(attr) => Some("style".to_string()),
+            Some(_) => None,
+        })
+        .filter(|name| name != "ref")
+        .collect::<Vec<_>>();
+    names.sort();
+    names.dedup();
+    names
+}
+
+fn add_children(children: &[Item], allowed: &AllowedChildren) -> Result<TokenStream, Error> {
+    children.iter().map(|c| add_child(c, allowed)).collect()
+}
+
+/// Generates code that appends `child` to the node in `NODE_IDENT`.
+fn add_child(child: &Item, allowed: &AllowedChildren) -> Result<TokenStream, Error> {
+    match child {
+        // The items of a loop are appended to the parent directly in each
+        // iteration, without creating a fragment or collecting them first.
+        Item::For(For { pattern, iterable, body }) => {
+            let add_body = add_children(body, allowed)?;
+            Ok(quote! {
+                for #pattern in #iterable {
+                    #add_body
+                }
+            })
+        }
+        child => {
+            let child = gen_item(child, allowed)?;
+            Ok(quote! {
+                ::domsl::Backend::append_child(#DOCUMENT_IDENT, &#NODE_IDENT, &#child);
+            })
+        }
+    }
+}
+
 
 const NODE_IDENT: DomslIdent = DomslIdent("__domsl_node");
 const DOCUMENT_IDENT: DomslIdent = DomslIdent("__domsl_document");
-const TMP_IDENT: DomslIdent = DomslIdent("__domsl_tmp");
+pub(crate) const TMP_IDENT: DomslIdent = DomslIdent("__domsl_tmp");
+pub(crate) const ATTR_BUF_IDENT: DomslIdent = DomslIdent("__domsl_attr_buf");
+pub(crate) const CLASSES_IDENT: DomslIdent = DomslIdent("__domsl_classes");
+pub(crate) const STYLES_IDENT: DomslIdent = DomslIdent("__domsl_styles");
 
 /// This is a small helper type that can be constructed as const-fn and
 /// implements `ToTokens`.
@@ -233,7 +679,7 @@ const TMP_IDENT: DomslIdent = DomslIdent("__domsl_tmp");
 /// `Ident` because `Ident::new` is not a const-fn and  because `lazy_static`
 /// or `thread_local` doesnt work as they rely on deref-coercion to work.
 /// That's what this type is for.
-struct DomslIdent(&'static str);
+pub(crate) struct DomslIdent(pub(crate) &'static str);
 
 impl quote::ToTokens for DomslIdent {
     fn to_tokens(&self, tokens: &mut TokenStream) {
@@ -241,6 +687,6 @@ impl quote::ToTokens for DomslIdent {
     }
 }
 
-fn starts_lowercase(tag: &Ident) -> bool {
+pub(crate) fn starts_lowercase(tag: &Name) -> bool {
     tag.to_string().chars().nth(0).expect("zero length ident").is_lowercase()
 }
@@ -1,4 +1,3 @@
-#![feature(drain_filter)]
 
 extern crate proc_macro;
 
@@ -11,6 +10,9 @@ mod component;
 mod error;
 mod jsx;
 mod html;
+mod parse;
+mod props;
+mod ssr;
 
 
 
@@ -18,7 +20,7 @@ mod html;
 pub fn jsx(input: TokenStream1) -> TokenStream1 {
     fn run(input: TokenStream) -> Result<TokenStream, Error> {
         let (document, body) = jsx::parse_outer(input)?;
-        let item = snax::parse(body.into())?;
+        let item = parse::parse(body)?;
 
         jsx::gen(&item, &document)
     }
@@ -29,9 +31,46 @@ pub fn jsx(input: TokenStream1) -> TokenStream1 {
 }
 
 
+#[proc_macro]
+pub fn html(input: TokenStream1) -> TokenStream1 {
+    fn run(input: TokenStream) -> Result<TokenStream, Error> {
+        let item = parse::parse(input)?;
+        ssr::gen(&item)
+    }
+
+    run(input.into())
+        .unwrap_or_else(|e| e.expr_error_tokens())
+        .into()
+}
+
+
+/// Defines the tables used by `domsl::ssr`. Not public API!
+#[doc(hidden)]
+#[proc_macro]
+pub fn __ssr_tables(_: TokenStream1) -> TokenStream1 {
+    ssr::gen_tables().into()
+}
+
+
 #[proc_macro_attribute]
 pub fn component(attrs: TokenStream1, input: TokenStream1) -> TokenStream1 {
     component::run(attrs.into(), input.into())
         .unwrap_or_else(|e| e.stmt_error_tokens())
         .into()
 }
+
+
+#[proc_macro_derive(Props, attributes(domsl))]
+pub fn props(input: TokenStream1) -> TokenStream1 {
+    props::derive(input.into())
+        .unwrap_or_else(|e| e.stmt_error_tokens())
+        .into()
+}
+
+
+#[proc_macro_derive(Component, attributes(domsl))]
+pub fn derive_component(input: TokenStream1) -> TokenStream1 {
+    component::derive(input.into())
+        .unwrap_or_else(|e| e.stmt_error_tokens())
+        .into()
+}
@@ -0,0 +1,595 @@
+//! Parsing the JSX-like markup into a small AST.
+
+use std::{fmt, iter::Peekable};
+
+use proc_macro2::{token_stream, Delimiter, Group, Ident, Span, TokenStream, TokenTree};
+
+use crate::error::Error;
+
+
+/// One item in the markup.
+#[derive(Debug)]
+pub(crate) enum Item {
+    /// An HTML tag or a component, e.g. `<div>...</div>` or `<br />`.
+    Tag(Tag),
+
+    /// A fragment `<> ... </>` with a list of children.
+    Fragment(Vec<Item>),
+
+    /// A literal or a Rust expression in braces, e.g. `"hello"` or `{ foo }`.
+    /// Blocks starting with `if`, `for` or `match` are only content if they
+    /// don't parse as markup, e.g. `{ if x { a } else { b } }`.
+    Content(TokenTree),
+
+    /// A conditional, e.g. `{if cond { <b>"yes"</b> } else { <i>"no"</i> }}`.
+    If(If),
+
+    /// A loop, e.g. `{for item in items { <li>{ item }</li> }}`.
+    For(For),
+
+    /// A match, e.g. `{match x { Some(x) => <b>{ x }</b>, None => "none" }}`.
+    Match(Match),
+
+    /// A shadow root, e.g. `<#shadow mode="closed">...</#shadow>`. Only valid
+    /// as direct child of an element.
+    Shadow(Shadow),
+}
+
+/// A conditional in braces. The branches contain markup, not Rust
+/// expressions. An `else if` is represented as an `else` branch that only
+/// contains another `If`. A missing `else` is an empty `else` branch.
+#[derive(Debug)]
+pub(crate) struct If {
+    /// Everything between `if` and the `{` of the first branch (including
+    /// the `let` of `if let`).
+    pub(crate) condition: TokenStream,
+    pub(crate) then_branch: Vec<Item>,
+    pub(crate) else_branch: Vec<Item>,
+}
+
+/// A `for` loop in braces. The body contains markup that is added once per
+/// iteration.
+#[derive(Debug)]
+pub(crate) struct For {
+    pub(crate) pattern: TokenStream,
+    pub(crate) iterable: TokenStream,
+    pub(crate) body: Vec<Item>,
+}
+
+/// A `match` in braces. The body of each arm is one item of markup.
+#[derive(Debug)]
+pub(crate) struct Match {
+    pub(crate) expr: TokenStream,
+    pub(crate) arms: Vec<Arm>,
+}
+
+#[derive(Debug)]
+pub(crate) struct Arm {
+    /// The pattern including the guard (`if ...`), if any.
+    pub(crate) pattern: TokenStream,
+    pub(crate) body: Item,
+}
+
+/// A tag, e.g. `<div class="foo">...</div>`. Self-closing tags simply have no
+/// children.
+#[derive(Debug)]
+pub(crate) struct Tag {
+    /// The tag name, which contains hyphens for custom elements (e.g.
+    /// `my-widget`).
+    pub(crate) name: Name,
+    pub(crate) attributes: Vec<Attribute>,
+    /// The expressions of spread attributes, e.g. `attrs` for `{..attrs}`.
+    pub(crate) spreads: Vec<TokenStream>,
+    pub(crate) children: Vec<Item>,
+}
+
+/// An attribute of a tag, e.g. `class="foo"` or `on:click={...}`.
+#[derive(Debug)]
+pub(crate) struct Attribute {
+    /// The optional namespace in front of the colon, e.g. `on` in
+    /// `on:click`.
+    pub(crate) namespace: Option<Ident>,
+    pub(crate) name: Name,
+    /// Either a literal or a group (usually a `{ }` block).
+    pub(crate) value: TokenTree,
+}
+
+/// The shadow root of an element with the children that are put into it.
+#[derive(Debug)]
+pub(crate) struct Shadow {
+    /// The span of `shadow` in `<#shadow>`.
+    pub(crate) span: Span,
+    /// Whether `mode="closed"` was given. The default mode is "open".
+    pub(crate) closed: bool,
+    pub(crate) children: Vec<Item>,
+}
+
+/// A name that can contain hyphens, e.g. `class`, `aria-label` or `data-0`.
+#[derive(Debug, Clone)]
+pub(crate) struct Name {
+    /// The parts between the hyphens. Never empty. The first part is always
+    /// an identifier, the others are identifiers or integer literals (which
+    /// is how Rust tokenizes e.g. the `0` and `2x` in `data-0` or `data-2x`).
+    parts: Vec<TokenTree>,
+}
+
+impl Name {
+    /// Returns the name as identifier, if it doesn't contain hyphens.
+    pub(crate) fn as_ident(&self) -> Option<&Ident> {
+        match &*self.parts {
+            [TokenTree::Ident(ident)] => Some(ident),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn span(&self) -> Span {
+        self.parts[0].span()
+    }
+}
+
+impl fmt::Display for Name {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, part) in self.parts.iter().enumerate() {
+            if i != 0 {
+                f.write_str("-")?;
+            }
+            part.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+pub(crate) type Tokens = Peekable<token_stream::IntoIter>;
+
+
+/// Parses the whole input of `jsx!` (without the `document =>` part) into one
+/// item.
+pub(crate) fn parse(input: TokenStream) -> Result<Item, Error> {
+    let mut tokens = input.into_iter().peekable();
+    let item = parse_item(&mut tokens)?;
+
+    if let Some(tt) = tokens.next() {
+        let msg = "expected end of input, but found this (use a fragment `<> ... </>` to \
+            return multiple items)";
+        return Err(Error::new(tt.span(), msg));
+    }
+
+    Ok(item)
+}
+
+fn parse_item(tokens: &mut Tokens) -> Result<Item, Error> {
+    match tokens.next() {
+        Some(TokenTree::Punct(ref p)) if p.as_char() == '<' => {
+            match tokens.next() {
+                // Fragment
+                Some(TokenTree::Punct(ref p)) if p.as_char() == '>' => {
+                    let children = parse_children(tokens, Closing::Fragment)?;
+                    Ok(Item::Fragment(children))
+                }
+                Some(TokenTree::Punct(ref p)) if p.as_char() == '#' => {
+                    parse_shadow(tokens).map(Item::Shadow)
+                }
+                Some(TokenTree::Ident(name)) => {
+                    let name = parse_name(tokens, name);
+                    parse_tag(tokens, name).map(Item::Tag)
+                }
+                Some(tt) => Err(Error::expected("tag name, `#shadow` or `>`", tt)),
+                None => Err(Error::eof()),
+            }
+        }
+        Some(TokenTree::Group(g)) if starts_with_keyword(&g, "if") => {
+            control_flow_or_content(g, |inner| parse_if(inner).map(Item::If))
+        }
+        Some(TokenTree::Group(g)) if starts_with_keyword(&g, "for") => {
+            control_flow_or_content(g, |inner| parse_for(inner).map(Item::For))
+        }
+        Some(TokenTree::Group(g)) if starts_with_keyword(&g, "match") => {
+            control_flow_or_content(g, |inner| parse_match(inner).map(Item::Match))
+        }
+        Some(tt @ TokenTree::Literal(_)) | Some(tt @ TokenTree::Group(_)) => {
+            Ok(Item::Content(tt))
+        }
+        Some(tt) => Err(Error::expected("`<`, literal or `{ }` block", tt)),
+        None => Err(Error::eof()),
+    }
+}
+
+/// Parses an `if` after the `if` keyword (which is already consumed).
+fn parse_if(tokens: &mut Tokens) -> Result<If, Error> {
+    let (condition, block) = parse_until_block(tokens, "condition")?;
+    let then_branch = parse_items(block.stream())?;
+
+    let else_branch = match tokens.peek() {
+        Some(TokenTree::Ident(ident)) if ident == "else" => {
+            tokens.next();
+            match tokens.next() {
+                Some(TokenTree::Ident(ref ident)) if ident == "if" => {
+                    vec![Item::If(parse_if(tokens)?)]
+                }
+                Some(TokenTree::Group(ref g)) if g.delimiter() == Delimiter::Brace => {
+                    parse_items(g.stream())?
+                }
+                Some(tt) => return Err(Error::expected("`if` or `{ }` block after `else`", tt)),
+                None => return Err(Error::eof()),
+            }
+        }
+        _ => vec![],
+    };
+
+    Ok(If { condition, then_branch, else_branch })
+}
+
+/// Parses a `for` loop after the `for` keyword (which is already consumed).
+fn parse_for(tokens: &mut Tokens) -> Result<For, Error> {
+    let mut pattern = TokenStream::new();
+    loop {
+        match tokens.next() {
+            Some(TokenTree::Ident(ref ident)) if ident == "in" => {
+                if pattern.is_empty() {
+                    return Err(Error::new(ident.span(), "expected pattern before `in`"));
+                }
+                break;
+            }
+            Some(tt) => pattern.extend(Some(tt)),
+            None => return Err(Error::eof()),
+        }
+    }
+
+    let (iterable, block) = parse_until_block(tokens, "expression")?;
+    let body = parse_items(block.stream())?;
+
+    Ok(For { pattern, iterable, body })
+}
+
+/// Parses a `match` after the `match` keyword (which is already consumed).
+fn parse_match(tokens: &mut Tokens) -> Result<Match, Error> {
+    let (expr, block) = parse_until_block(tokens, "expression")?;
+
+    let mut arms = Vec::new();
+    let mut tokens = block.stream().into_iter().peekable();
+    while tokens.peek().is_some() {
+        let mut pattern = TokenStream::new();
+        loop {
+            match tokens.next() {
+                Some(TokenTree::Punct(ref p)) if p.as_char() == '=' && is_punct(&tokens, '>') => {
+                    if pattern.is_empty() {
+                        return Err(Error::new(p.span(), "expected pattern before `=>`"));
+                    }
+                    tokens.next();
+                    break;
+                }
+                Some(tt) => pattern.extend(Some(tt)),
+                None => return Err(Error::eof()),
+            }
+        }
+
+        let body = parse_item(&mut tokens)?;
+        arms.push(Arm { pattern, body });
+
+        // The comma after an arm is optional, as the end of the item is
+        // always clear.
+        if is_punct(&tokens, ',') {
+            tokens.next();
+        }
+    }
+
+    Ok(Match { expr, arms })
+}
+
+/// Collects all tokens up to the next `{ }` block and returns them together
+/// with that block. Like in Rust, the tokens end at the first `{` (struct
+/// literals are not allowed in conditions for exactly this reason). `what`
+/// is used in the error if there are no tokens before the block.
+fn parse_until_block(tokens: &mut Tokens, what: &str) -> Result<(TokenStream, Group), Error> {
+    let mut before = TokenStream::new();
+    loop {
+        match tokens.next() {
+            Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Brace => {
+                if before.is_empty() {
+                    return Err(Error::new(g.span(), &format!("expected {} before `{{`", what)));
+                }
+                return Ok((before, g));
+            }
+            Some(tt) => before.extend(Some(tt)),
+            None => return Err(Error::eof()),
+        }
+    }
+}
+
+/// Parses items until the end of `input`. Used for the contents of blocks
+/// like the branches of an `if`.
+fn parse_items(input: TokenStream) -> Result<Vec<Item>, Error> {
+    let mut tokens = input.into_iter().peekable();
+    let mut items = Vec::new();
+    while tokens.peek().is_some() {
+        items.push(parse_item(&mut tokens)?);
+    }
+
+    Ok(items)
+}
+
+/// Parses the `{ }` block `group` starting with a keyword (which is skipped)
+/// with `parse`. If that fails and the block doesn't contain markup, it's a
+/// normal Rust expression (e.g. `{ if x { a } else { b } }` with string
+/// variables) and is returned as content.
+fn control_flow_or_content(
+    group: Group,
+    parse: impl FnOnce(&mut Tokens) -> Result<Item, Error>,
+) -> Result<Item, Error> {
+    let mut inner = group.stream().into_iter().peekable();
+    inner.next();
+    let result = parse(&mut inner).and_then(|item| {
+        expect_end(&mut inner)?;
+        Ok(item)
+    });
+
+    match result {
+        Err(_) if !contains_markup(group.stream()) => Ok(Item::Content(TokenTree::Group(group))),
+        other => other,
+    }
+}
+
+/// Returns whether `tokens` contain markup, i.e. a `<` at the start or after
+/// `=>`, also in nested `{ }` blocks.
+fn contains_markup(tokens: TokenStream) -> bool {
+    let mut item_expected = true;
+    let mut after_eq = false;
+    for tt in tokens {
+        match &tt {
+            TokenTree::Punct(p) if p.as_char() == '<' && item_expected => return true,
+            TokenTree::Group(g)
+                if g.delimiter() == Delimiter::Brace && contains_markup(g.stream()) =>
+            {
+                return true;
+            }
+            _ => {}
+        }
+
+        item_expected = after_eq && matches!(&tt, TokenTree::Punct(p) if p.as_char() == '>');
+        after_eq = matches!(&tt, TokenTree::Punct(p) if p.as_char() == '=');
+    }
+
+    false
+}
+
+/// Returns whether `group` is a `{ }` block starting with the given keyword.
+fn starts_with_keyword(group: &Group, keyword: &str) -> bool {
+    group.delimiter() == Delimiter::Brace && match group.stream().into_iter().next() {
+        Some(TokenTree::Ident(ident)) => ident == keyword,
+        _ => false,
+    }
+}
+
+/// Parses a tag after its name. The `<` and the name are already consumed.
+fn parse_tag(tokens: &mut Tokens, name: Name) -> Result<Tag, Error> {
+    let mut attributes = Vec::new();
+    let mut spreads = Vec::new();
+    loop {
+        match tokens.next() {
+            Some(TokenTree::Punct(ref p)) if p.as_char() == '>' => break,
+            Some(TokenTree::Punct(ref p)) if p.as_char() == '/' => {
+                expect_punct(tokens, '>')?;
+                return Ok(Tag { name, attributes, spreads, children: vec![] });
+            }
+            Some(TokenTree::Ident(ident)) => attributes.push(parse_attribute(tokens, ident)?),
+            Some(TokenTree::Group(ref g)) if g.delimiter() == Delimiter::Brace => {
+                spreads.push(parse_spread(g)?);
+            }
+            Some(tt) => return Err(Error::expected("attribute, `{..}`, `>` or `/>`", tt)),
+            None => return Err(Error::eof()),
+        }
+    }
+
+    let children = parse_children(tokens, Closing::Tag(&name))?;
+    Ok(Tag { name, attributes, spreads, children })
+}
+
+/// Parses a spread attribute `{..expr}` and returns `expr`.
+fn parse_spread(group: &Group) -> Result<TokenStream, Error> {
+    let mut tokens = group.stream().into_iter().peekable();
+    for _ in 0..2 {
+        match tokens.next() {
+            Some(TokenTree::Punct(ref p)) if p.as_char() == '.' => {}
+            _ => {
+                let msg = "expected spread attribute `{..expr}` (attribute values need a name)";
+                return Err(Error::new(group.span(), msg));
+            }
+        }
+    }
+
+    let expr = tokens.collect::<TokenStream>();
+    if expr.is_empty() {
+        return Err(Error::new(group.span(), "expected expression after `..`"));
+    }
+
+    Ok(expr)
+}
+
+/// Parses a shadow root after the `<#` (which is already consumed).
+fn parse_shadow(tokens: &mut Tokens) -> Result<Shadow, Error> {
+    let span = match tokens.next() {
+        Some(TokenTree::Ident(ref ident)) if ident == "shadow" => ident.span(),
+        Some(tt) => return Err(Error::expected("`shadow` (after `<#`)", tt)),
+        None => return Err(Error::eof()),
+    };
+
+    let mut closed = false;
+    loop {
+        match tokens.next() {
+            Some(TokenTree::Punct(ref p)) if p.as_char() == '>' => break,
+            Some(TokenTree::Punct(ref p)) if p.as_char() == '/' => {
+                expect_punct(tokens, '>')?;
+                return Ok(Shadow { span, closed, children: vec![] });
+            }
+            Some(TokenTree::Ident(ref ident)) if ident == "mode" => {
+                expect_punct(tokens, '=')?;
+                let value = tokens.next().ok_or_else(Error::eof)?;
+                closed = match syn::parse2::<syn::LitStr>(value.clone().into()) {
+                    Ok(ref lit) if lit.value() == "open" => false,
+                    Ok(ref lit) if lit.value() == "closed" => true,
+                    _ => return Err(Error::expected("`\"open\"` or `\"closed\"`", value)),
+                };
+            }
+            Some(tt) => return Err(Error::expected("`mode`, `>` or `/>`", tt)),
+            None => return Err(Error::eof()),
+        }
+    }
+
+    let children = parse_children(tokens, Closing::Shadow)?;
+    Ok(Shadow { span, closed, children })
+}
+
+/// Parses an attribute after its first identifier (which is already
+/// consumed).
+fn parse_attribute(tokens: &mut Tokens, first: Ident) -> Result<Attribute, Error> {
+    let first = parse_name(tokens, first);
+    let (namespace, name) = match tokens.peek() {
+        Some(TokenTree::Punct(p)) if p.as_char() == ':' => {
+            tokens.next();
+            let namespace = match first.as_ident() {
+                Some(ident) => ident.clone(),
+                None => return Err(Error::new(first.span(), "namespaces can't contain `-`")),
+            };
+            match tokens.next() {
+                Some(TokenTree::Ident(name)) => (Some(namespace), parse_name(tokens, name)),
+                Some(tt) => return Err(Error::expected("attribute name", tt)),
+                None => return Err(Error::eof()),
+            }
+        }
+        _ => (None, first),
+    };
+
+    expect_punct(tokens, '=')?;
+    let value = match tokens.next() {
+        Some(tt @ TokenTree::Literal(_)) | Some(tt @ TokenTree::Group(_)) => tt,
+        Some(tt) => return Err(Error::expected("literal or `{ }` block as attribute value", tt)),
+        None => return Err(Error::eof()),
+    };
+
+    Ok(Attribute { namespace, name, value })
+}
+
+/// Parses a name that might contain hyphens, like `aria-label`. The first
+/// identifier is already consumed.
+pub(crate) fn parse_name(tokens: &mut Tokens, first: Ident) -> Name {
+    let mut parts = vec![TokenTree::Ident(first)];
+    loop {
+        let mut lookahead = tokens.clone();
+        let part = match (lookahead.next(), lookahead.next()) {
+            (Some(TokenTree::Punct(ref p)), Some(part)) if p.as_char() == '-' => part,
+            _ => break,
+        };
+
+        let is_name_part = match &part {
+            TokenTree::Ident(_) => true,
+            TokenTree::Literal(lit) => lit.to_string().chars().all(|c| c.is_ascii_alphanumeric()),
+            _ => false,
+        };
+        if !is_name_part {
+            break;
+        }
+
+        tokens.next();
+        tokens.next();
+        parts.push(part);
+    }
+
+    Name { parts }
+}
+
+/// What ends a list of children.
+#[derive(Clone, Copy)]
+enum Closing<'a> {
+    /// `</>`
+    Fragment,
+    /// `</name>`
+    Tag(&'a Name),
+    /// `</#shadow>`
+    Shadow,
+}
+
+impl fmt::Display for Closing<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Closing::Fragment => f.write_str("closing fragment `</>`"),
+            Closing::Tag(name) => write!(f, "closing tag `</{}>`", name),
+            Closing::Shadow => f.write_str("closing tag `</#shadow>`"),
+        }
+    }
+}
+
+/// Parses children until (and including) the closing tag described by
+/// `closing`.
+fn parse_children(tokens: &mut Tokens, closing: Closing<'_>) -> Result<Vec<Item>, Error> {
+    let mut children = Vec::new();
+    loop {
+        if is_closing_tag(tokens) {
+            // Skip `<` and `/`
+            tokens.next();
+            tokens.next();
+            let next = tokens.next();
+            let span = next.as_ref().map(|tt| tt.span());
+            let matches = match (closing, next) {
+                (Closing::Fragment, Some(TokenTree::Punct(ref p))) => p.as_char() == '>',
+                (Closing::Tag(name), Some(TokenTree::Ident(close))) => {
+                    parse_name(tokens, close).to_string() == name.to_string()
+                        && expect_punct(tokens, '>').is_ok()
+                }
+                (Closing::Shadow, Some(TokenTree::Punct(ref p))) if p.as_char() == '#' => {
+                    match tokens.next() {
+                        Some(TokenTree::Ident(ref ident)) => {
+                            ident == "shadow" && expect_punct(tokens, '>').is_ok()
+                        }
+                        _ => false,
+                    }
+                }
+                (_, Some(_)) => false,
+                (_, None) => return Err(Error::eof()),
+            };
+
+            if !matches {
+                let msg = format!("expected {}", closing);
+                return Err(Error::new(span.unwrap(), &msg));
+            }
+            break;
+        }
+
+        children.push(parse_item(tokens)?);
+    }
+
+    Ok(children)
+}
+
+/// Returns whether the next token is the punctuation `c`.
+fn is_punct(tokens: &Tokens, c: char) -> bool {
+    match tokens.clone().next() {
+        Some(TokenTree::Punct(p)) => p.as_char() == c,
+        _ => false,
+    }
+}
+
+/// Returns whether the next two tokens are `<` and `/`.
+fn is_closing_tag(tokens: &Tokens) -> bool {
+    let mut tokens = tokens.clone();
+    match (tokens.next(), tokens.next()) {
+        (Some(TokenTree::Punct(p0)), Some(TokenTree::Punct(p1))) => {
+            p0.as_char() == '<' && p1.as_char() == '/'
+        }
+        _ => false,
+    }
+}
+
+fn expect_end(tokens: &mut Tokens) -> Result<(), Error> {
+    match tokens.next() {
+        Some(tt) => Err(Error::new(tt.span(), "expected end of block, but found this")),
+        None => Ok(()),
+    }
+}
+
+fn expect_punct(tokens: &mut Tokens, c: char) -> Result<(), Error> {
+    match tokens.next() {
+        Some(TokenTree::Punct(ref p)) if p.as_char() == c => Ok(()),
+        Some(tt) => Err(Error::expected(&format!("`{}`", c), tt)),
+        None => Err(Error::eof()),
+    }
+}
@@ -0,0 +1,325 @@
+//! Generating the builders through which `jsx!` creates components.
+//!
+//! For a component struct `Foo`, `jsx!` generates
+//! `builder(|Foo { .. }| ()).a(...).b(...).__domsl_build()` instead of a
+//! struct literal, so that props can be omitted. The builder is given by the
+//! `Props` impl of `Foo`. It tracks which props were set in its type
+//! parameters (`Unset` or `Set<T>`), so missing required props are still
+//! compile errors. For those, `__domsl_build` requires one generated trait per
+//! required prop, whose `on_unimplemented` message names the prop. The builder
+//! and these traits are defined in an anonymous constant, so they don't
+//! pollute the module of the component.
+//!
+//! Props are set through methods of the builder named like the prop, one
+//! per prop, so `jsx!` doesn't have to generate anything but the method call.
+//! Unknown props result in rustc's "no method named `x` found for struct
+//! `__FooProps`" error on the prop, including suggestions for typos.
+
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{quote, quote_spanned};
+use syn::{
+    parse::{Parse, ParseStream},
+    spanned::Spanned,
+    Generics, Token,
+};
+
+use crate::error::Error;
+
+
+/// A prop of a component, i.e. a field of the component struct.
+pub(crate) struct Prop {
+    pub(crate) ident: Ident,
+    pub(crate) ty: syn::Type,
+
+    /// The expression used if the prop is omitted, or `None` if the prop is
+    /// required.
+    pub(crate) default: Option<TokenStream>,
+}
+
+impl Prop {
+    /// Creates a prop with the default given by a `#[domsl(default)]`
+    /// attribute (`default_attr`). Props of type `Option<T>` are optional
+    /// even without attribute.
+    pub(crate) fn new(
+        ident: Ident,
+        ty: syn::Type,
+        default_attr: Option<Option<syn::Expr>>,
+    ) -> Self {
+        let default = match default_attr {
+            Some(Some(expr)) => Some(quote_spanned!(expr.span()=> #expr)),
+            Some(None) => Some(quote_spanned!(ty.span()=> ::std::default::Default::default())),
+            None if is_option(&ty) => Some(quote! { ::std::option::Option::None }),
+            None => None,
+        };
+
+        Self { ident, ty, default }
+    }
+}
+
+/// A `#[domsl(...)]` attribute on a component parameter or field.
+pub(crate) enum PropAttr {
+    /// `#[domsl(children)]`
+    Children,
+
+    /// `#[domsl(backend)]`
+    Backend,
+
+    /// `#[domsl(default)]` or `#[domsl(default = expr)]`
+    Default(Option<syn::Expr>),
+}
+
+impl Parse for PropAttr {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let ident = input.parse::<Ident>()?;
+        match &*ident.to_string() {
+            "children" => Ok(PropAttr::Children),
+            "backend" => Ok(PropAttr::Backend),
+            "default" if input.is_empty() => Ok(PropAttr::Default(None)),
+            "default" => {
+                input.parse::<Token![=]>()?;
+                Ok(PropAttr::Default(Some(input.parse()?)))
+            }
+            other => {
+                let msg = format!("unknown domsl attribute '{}'", other);
+                Err(syn::Error::new(ident.span(), msg))
+            }
+        }
+    }
+}
+
+/// Returns whether `ty` is (syntactically) an `Option<T>`.
+fn is_option(ty: &syn::Type) -> bool {
+    let path = match ty {
+        syn::Type::Path(syn::TypePath { qself: None, path }) => path,
+        _ => return false,
+    };
+
+    let segments = path.segments.iter().map(|s| s.ident.to_string()).collect::<Vec<_>>();
+    let is_option_path = match &*segments.join("::") {
+        "Option" | "option::Option" | "std::option::Option" | "core::option::Option" => true,
+        _ => false,
+    };
+
+    let last = path.segments.last().expect("path without segments");
+    is_option_path && match &last.arguments {
+        syn::PathArguments::AngleBracketed(args) => args.args.len() == 1,
+        _ => false,
+    }
+}
+
+/// Derives the builder for a struct with `#[derive(Props)]`.
+pub(crate) fn derive(input: TokenStream) -> Result<TokenStream, Error> {
+    let input: syn::DeriveInput = syn::parse2(input)?;
+    let props = struct_props(&input, "Props")?;
+    Ok(gen_builder(&input.ident, &input.vis, &input.generics, &props, None))
+}
+
+/// Returns the props of the struct `input` (one per field), for deriving
+/// the trait `derived`.
+pub(crate) fn struct_props(input: &syn::DeriveInput, derived: &str) -> Result<Vec<Prop>, Error> {
+    let fields = match &input.data {
+        syn::Data::Struct(syn::DataStruct { fields: syn::Fields::Named(fields), .. }) => {
+            fields.named.iter().collect()
+        }
+        syn::Data::Struct(syn::DataStruct { fields: syn::Fields::Unit, .. }) => vec![],
+        _ => {
+            let msg = format!("`{}` can only be derived for structs with named fields", derived);
+            return Err(Error::new(input.ident.span(), &msg));
+        }
+    };
+
+    fields.into_iter().map(|field| {
+        let mut default = None;
+        for attr in field.attrs.iter().filter(|attr| attr.path.is_ident("domsl")) {
+            match attr.parse_args::<PropAttr>()? {
+                PropAttr::Default(expr) => default = Some(expr),
+                PropAttr::Children | PropAttr::Backend => {
+                    let msg = "`#[domsl(children)]` and `#[domsl(backend)]` can only be used \
+                        on parameters of `#[domsl::component]` functions";
+                    return Err(Error::new(attr.span(), msg));
+                }
+            }
+        }
+
+        let ident = field.ident.clone().expect("named field without name");
+        Ok(Prop::new(ident, field.ty.clone(), default))
+    }).collect()
+}
+
+/// Generates the builder for the component struct `name` with the given
+/// generics and props (its fields). See the module documentation. If the
+/// struct has an additional `PhantomData` field, its name is `phantom`.
+pub(crate) fn gen_builder(
+    name: &Ident,
+    vis: &syn::Visibility,
+    generics: &Generics,
+    props: &[Prop],
+    phantom: Option<&Ident>,
+) -> TokenStream {
+    let builder = Ident::new(&format!("__{}Props", name), name.span());
+    let idents = props.iter().map(|p| &p.ident).collect::<Vec<_>>();
+    let states = (0..props.len())
+        .map(|i| Ident::new(&format!("__DomslProp{}", i), Span::call_site()))
+        .collect::<Vec<_>>();
+    let args = generic_args(generics);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let unset = quote! { ::domsl::props::Unset };
+    let marker = quote! { __domsl_marker: ::std::marker::PhantomData };
+
+    // The builder itself and the function creating it.
+    let builder_generics = with_params(generics, &states);
+    let (builder_impl_generics, _, _) = builder_generics.split_for_impl();
+    let initial_ty = builder_type(&builder, &args, props.iter().map(|_| unset.clone()));
+    let mut out = quote! {
+        #vis struct #builder #builder_impl_generics #where_clause {
+            #( #idents: #states, )*
+            __domsl_marker: ::std::marker::PhantomData<fn() -> #name #ty_generics>,
+        }
+
+        impl #impl_generics ::domsl::props::Props for #name #ty_generics #where_clause {
+            type Builder = #initial_ty;
+            fn __domsl_props() -> #initial_ty {
+                #builder {
+                    #( #idents: ::domsl::props::Unset, )*
+                    #marker,
+                }
+            }
+        }
+    };
+
+    // One setter method per prop, named like the prop. Each only exists if
+    // the prop is not set yet.
+    for (i, prop) in props.iter().enumerate() {
+        let Prop { ident, ty, .. } = prop;
+        let other_states = states.iter().enumerate()
+            .filter(|&(j, _)| j != i)
+            .map(|(_, state)| state.clone())
+            .collect::<Vec<_>>();
+        let setter_generics = with_params(generics, &other_states);
+        let (setter_impl_generics, _, _) = setter_generics.split_for_impl();
+
+        let state_or = |replacement: TokenStream| states.iter().enumerate()
+            .map(|(j, state)| if i == j { replacement.clone() } else { quote! { #state } })
+            .collect::<Vec<_>>();
+        let self_ty = builder_type(&builder, &args, state_or(unset.clone()));
+        let out_ty = builder_type(&builder, &args, state_or(quote! { ::domsl::props::Set<#ty> }));
+        let others = idents.iter().filter(|other| **other != ident).collect::<Vec<_>>();
+
+        out.extend(quote! {
+            #[allow(dead_code)]
+            impl #setter_impl_generics #self_ty #where_clause {
+                #vis fn #ident(self, value: #ty) -> #out_ty {
+                    #builder {
+                        #ident: ::domsl::props::Set(value),
+                        #( #others: self.#others, )*
+                        #marker,
+                    }
+                }
+            }
+        });
+    }
+
+    // `__domsl_build` can be called in every state, but requires all required
+    // props to be set. That's checked with one trait per required prop, so
+    // that the error names the missing prop.
+    let mut bounds = Vec::new();
+    let mut values = Vec::new();
+    for (Prop { ident, ty, default }, state) in props.iter().zip(&states) {
+        match default {
+            Some(default) => {
+                bounds.push(quote! { #state: ::domsl::props::Optional<#ty> });
+                values.push(quote! {
+                    ::domsl::props::Optional::or_else(self.#ident, || #default)
+                });
+            }
+            None => {
+                let prop_name = ident.to_string().trim_start_matches("r#").to_string();
+                let required = Ident::new(&format!("__{}Prop_{}", name, prop_name), name.span());
+                let message = format!("missing prop `{}` for component `{}`", prop_name, name);
+                let label = format!("`<{}>` requires the prop `{}`", name, prop_name);
+                out.extend(quote! {
+                    #[allow(non_camel_case_types)]
+                    #[diagnostic::on_unimplemented(message = #message, label = #label)]
+                    #vis trait #required<T> {
+                        fn __domsl_value(self) -> T;
+                    }
+
+                    impl<T> #required<T> for ::domsl::props::Set<T> {
+                        fn __domsl_value(self) -> T {
+                            self.0
+                        }
+                    }
+                });
+
+                bounds.push(quote! { #state: #required<#ty> });
+                values.push(quote! { #required::__domsl_value(self.#ident) });
+            }
+        }
+    }
+
+    let phantom = phantom.map(|ident| quote! { #ident: ::std::marker::PhantomData, });
+    let all_states_ty = builder_type(&builder, &args, states.iter().map(|s| quote! { #s }));
+    out.extend(quote! {
+        #[allow(dead_code)]
+        impl #builder_impl_generics #all_states_ty #where_clause {
+            #vis fn __domsl_build(self) -> #name #ty_generics
+            where
+                #( #bounds, )*
+            {
+                #name {
+                    #( #idents: #values, )*
+                    #phantom
+                }
+            }
+        }
+    });
+
+    // Everything is wrapped in an anonymous constant, so that the builder and
+    // the traits don't end up in the module of the component.
+    quote! {
+        const _: () = {
+            #out
+        };
+    }
+}
+
+/// Returns `generics` with the additional type parameters `params`.
+fn with_params(generics: &Generics, params: &[Ident]) -> Generics {
+    let mut out = generics.clone();
+    out.params.extend(params.iter().map(|p| syn::GenericParam::Type(p.clone().into())));
+    out
+}
+
+/// Returns the generic arguments corresponding to the parameters of
+/// `generics`, e.g. `'a` and `T` for `<'a, T: Clone>`.
+fn generic_args(generics: &Generics) -> Vec<TokenStream> {
+    generics.params.iter().map(|param| match param {
+        syn::GenericParam::Lifetime(l) => {
+            let lifetime = &l.lifetime;
+            quote! { #lifetime }
+        }
+        syn::GenericParam::Type(t) => {
+            let ident = &t.ident;
+            quote! { #ident }
+        }
+        syn::GenericParam::Const(c) => {
+            let ident = &c.ident;
+            quote! { #ident }
+        }
+    }).collect()
+}
+
+/// Returns the type of the builder with the given generic arguments and
+/// prop states.
+fn builder_type(
+    builder: &Ident,
+    args: &[TokenStream],
+    states: impl IntoIterator<Item = TokenStream>,
+) -> TokenStream {
+    let all = args.iter().cloned().chain(states).collect::<Vec<_>>();
+    if all.is_empty() {
+        quote! { #builder }
+    } else {
+        quote! { #builder<#( #all ),*> }
+    }
+}
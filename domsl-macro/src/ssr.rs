@@ -0,0 +1,351 @@
+//! Code generation for `html!`, which renders the markup into an HTML string
+//! instead of creating DOM nodes.
+
+use std::{mem, slice};
+
+use proc_macro2::{TokenStream, TokenTree};
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+
+use crate::{
+    error::Error,
+    html::{self, AllowedChildren, TagInfo},
+    jsx::{self, is_shadow, starts_lowercase, DomslIdent, ATTR_BUF_IDENT, TMP_IDENT},
+    parse::{Arm, Attribute, For, If, Item, Match, Shadow, Tag},
+};
+
+
+const BUF_IDENT: DomslIdent = DomslIdent("__domsl_buf");
+
+/// The main entry point to generate the output code.
+pub(crate) fn gen(root: &Item) -> Result<TokenStream, Error> {
+    let mut out = Output::default();
+    gen_item(root, &AllowedChildren::Any, false, &mut out)?;
+    let code = out.finish();
+    let track_registry = html::custom::track_registry();
+
+    Ok(quote! {{
+        #track_registry
+        use domsl::{
+            specialization_hack::{
+                EscapeDisplayKind, EscapeStrKind, IterEscapeDisplayKind, IterEscapeStrKind,
+                HtmlWrap, IterRawHtmlKind, RawHtmlKind,
+            }
+        };
+
+        let mut #BUF_IDENT = ::std::string::String::new();
+        #code
+        ::domsl::ssr::Html::from_raw(#BUF_IDENT)
+    }})
+}
+
+/// Generates code that writes `item` into the buffer. `allowed` specifies
+/// what children are allowed at the position of `item`. `raw_text` is true
+/// inside of elements whose content is not HTML (`<script>` and `<style>`).
+fn gen_item(
+    item: &Item,
+    allowed: &AllowedChildren,
+    raw_text: bool,
+    out: &mut Output,
+) -> Result<(), Error> {
+    match item {
+        Item::Tag(tag) if starts_lowercase(&tag.name) => gen_html_tag(tag, allowed, out)?,
+        Item::Tag(tag) => gen_component(tag, out)?,
+        Item::Fragment(children) => {
+            for child in children {
+                gen_item(child, allowed, raw_text, out)?;
+            }
+        }
+        Item::Content(tt) => {
+            allowed.check_content(tt)?;
+
+            // Raw text is always escaped at runtime, as whether something
+            // has to be escaped there depends on what was written before.
+            match string_literal(tt) {
+                Some(s) if !raw_text => out.push_static(&escape(&s, false)),
+                _ => out.push_code(quote! {{
+                    let #TMP_IDENT = (#tt);
+                    (&&&&&HtmlWrap(&#TMP_IDENT)).domsl_html_kind()
+                        .write_html(#TMP_IDENT, &mut #BUF_IDENT, #raw_text);
+                }}),
+            }
+        }
+        Item::If(If { condition, then_branch, else_branch }) => {
+            let then_branch = gen_branch(then_branch, allowed, raw_text)?;
+            let else_branch = gen_branch(else_branch, allowed, raw_text)?;
+            out.push_code(quote! {
+                if #condition { #then_branch } else { #else_branch }
+            });
+        }
+        Item::For(For { pattern, iterable, body }) => {
+            let body = gen_branch(body, allowed, raw_text)?;
+            out.push_code(quote! {
+                for #pattern in #iterable { #body }
+            });
+        }
+        Item::Match(Match { expr, arms }) => {
+            let arms = arms.iter().map(|Arm { pattern, body }| {
+                let body = gen_branch(slice::from_ref(body), allowed, raw_text)?;
+                Ok(quote! { #pattern => { #body } })
+            }).collect::<Result<TokenStream, Error>>()?;
+
+            out.push_code(quote! {
+                match #expr { #arms }
+            });
+        }
+
+        // Shadow roots are handled by `gen_html_tag`.
+        Item::Shadow(shadow) => return Err(Error::misplaced_shadow(shadow)),
+    }
+
+    Ok(())
+}
+
+/// Generates the code writing all `items` of a branch (e.g. of an `if` or a
+/// `match`) or of a loop body.
+fn gen_branch(
+    items: &[Item],
+    allowed: &AllowedChildren,
+    raw_text: bool,
+) -> Result<TokenStream, Error> {
+    let mut out = Output::default();
+    for item in items {
+        gen_item(item, allowed, raw_text, &mut out)?;
+    }
+
+    Ok(out.finish())
+}
+
+fn gen_html_tag(tag: &Tag, allowed: &AllowedChildren, out: &mut Output) -> Result<(), Error> {
+    let info = &*TagInfo::from_name(&tag.name, allowed.namespace())?;
+    allowed.check_tag(info, &tag.name)?;
+
+    out.push_static(&format!("<{}", info.name));
+    let dynamic_classes = jsx::has_dynamic_classes(tag);
+    let dynamic_styles = jsx::has_dynamic_styles(tag);
+    let attrs = tag.attributes.iter()
+        .filter(|attr| !(dynamic_classes && jsx::is_class(attr)))
+        .filter(|attr| !(dynamic_styles && jsx::is_style(attr)));
+    for attr in attrs {
+        gen_attribute(attr, info, out)?;
+    }
+    if dynamic_classes {
+        let classes = jsx::gen_classes(tag);
+        out.push_code(quote! {
+            if let Some(#TMP_IDENT) = ::domsl::attribute::Classes::value(&#classes) {
+                #BUF_IDENT.push_str(" class=\"");
+                // Writing into a string never fails.
+                ::std::fmt::Write::write_str(
+                    &mut ::domsl::ssr::Escaper::attribute(&mut #BUF_IDENT),
+                    #TMP_IDENT,
+                ).unwrap();
+                #BUF_IDENT.push('"');
+            }
+        });
+    }
+    if dynamic_styles {
+        let styles = jsx::gen_styles(tag)?;
+        out.push_code(quote! {
+            if let Some(#TMP_IDENT) = ::domsl::attribute::Styles::value(&#styles) {
+                #BUF_IDENT.push_str(" style=\"");
+                // Writing into a string never fails.
+                ::std::fmt::Write::write_str(
+                    &mut ::domsl::ssr::Escaper::attribute(&mut #BUF_IDENT),
+                    #TMP_IDENT,
+                ).unwrap();
+                #BUF_IDENT.push('"');
+            }
+        });
+    }
+    let explicit = jsx::explicit_attribute_names(tag);
+    let boolean = info.boolean_attributes();
+    for spread in &tag.spreads {
+        let spread_fn = quote_spanned!(spread.span()=> ::domsl::attribute::spread);
+        out.push_code(quote! {
+            #spread_fn((#spread), &[ #( #explicit ),* ], &[ #( #boolean ),* ], |name, value| {
+                #BUF_IDENT.push(' ');
+                #BUF_IDENT.push_str(name);
+                #BUF_IDENT.push_str("=\"");
+                // Writing into a string never fails.
+                ::std::fmt::Write::write_str(
+                    &mut ::domsl::ssr::Escaper::attribute(&mut #BUF_IDENT),
+                    value,
+                ).unwrap();
+                #BUF_IDENT.push('"');
+            });
+        });
+    }
+    out.push_static(">");
+
+    // This also rejects shadow roots on elements that can't host one (like
+    // void elements), so it has to come before the early return below.
+    let shadow = jsx::shadow_child(tag, info)?;
+
+    // Void elements don't have an end tag. Children are already rejected by
+    // the `allowed_children` check.
+    if info.is_void() {
+        return Ok(());
+    }
+
+    // The shadow root is rendered as declarative shadow DOM, which has to
+    // be the first child of the element.
+    if let Some(Shadow { closed, children, .. }) = shadow {
+        let mode = if *closed { "closed" } else { "open" };
+        out.push_static(&format!("<template shadowrootmode=\"{}\">", mode));
+        for child in children {
+            gen_item(child, &AllowedChildren::Any, false, out)?;
+        }
+        out.push_static("</template>");
+    }
+
+    let allowed = info.allowed_children(allowed);
+    let raw_text = info.has_raw_text();
+    for child in tag.children.iter().filter(|child| !is_shadow(child)) {
+        gen_item(child, &allowed, raw_text, out)?;
+    }
+    out.push_static(&format!("</{}>", info.name));
+
+    Ok(())
+}
+
+/// Components are rendered with the `Renderer` backend. Their children are
+/// rendered to `Html` first, as that's what we are doing anyway.
+fn gen_component(tag: &Tag, out: &mut Output) -> Result<(), Error> {
+    let component = jsx::gen_component_struct(tag)?;
+    let children = tag.children.iter()
+        .map(|child| gen(child).map(|html| quote! { ::domsl::ssr::Node::from(#html) }))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let render_call = quote_spanned!(tag.name.span()=>
+        ::domsl::backend::render_component
+    );
+
+    out.push_code(quote! {{
+        let #TMP_IDENT = #render_call(
+            &::domsl::ssr::Renderer,
+            #component,
+            vec![ #( #children ),* ],
+        );
+        #TMP_IDENT.write_html(&mut #BUF_IDENT);
+    }});
+
+    Ok(())
+}
+
+fn gen_attribute(attr: &Attribute, info: &TagInfo, out: &mut Output) -> Result<(), Error> {
+    let Attribute { name, value, .. } = attr;
+    match &attr.namespace {
+        // Event listeners and DOM properties don't make sense in a string, so
+        // they are ignored.
+        Some(namespace) if namespace == "on" || namespace == "prop" => return Ok(()),
+        Some(namespace) => return Err(Error::unknown_namespace(namespace)),
+        // There are no elements to reference in a string.
+        None if name.to_string() == "ref" => return Ok(()),
+        None => info.check_attribute(name)?,
+    }
+
+    match string_literal(value) {
+        Some(s) => out.push_static(&format!(" {}=\"{}\"", name, escape(&s, true))),
+        None => {
+            // The attribute is only written if `AttributeValue` says so.
+            let start = format!(" {}=\"", name);
+            let attribute_value = jsx::attribute_value_fn(info, &name.to_string(), value.span());
+            out.push_code(quote! {{
+                let mut #ATTR_BUF_IDENT = ::std::string::String::new();
+                if let Some(#TMP_IDENT) = #attribute_value(&(#value), &mut #ATTR_BUF_IDENT) {
+                    #BUF_IDENT.push_str(#start);
+                    // Writing into a string never fails.
+                    ::std::fmt::Write::write_str(
+                        &mut ::domsl::ssr::Escaper::attribute(&mut #BUF_IDENT),
+                        #TMP_IDENT,
+                    ).unwrap();
+                    #BUF_IDENT.push('"');
+                }
+            }});
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the value of `tt` if it is a string literal.
+fn string_literal(tt: &TokenTree) -> Option<String> {
+    syn::parse2::<syn::LitStr>(tt.clone().into()).ok().map(|lit| lit.value())
+}
+
+/// The characters that are replaced when escaping text and attribute values.
+/// The `bool` is true for replacements only done in attribute values (which
+/// are always in double quotes). This is the only definition of these rules:
+/// `domsl::ssr::Escaper` gets this table via `gen_tables`.
+const ESCAPES: &[(char, &str, bool)] = &[
+    ('&', "&amp;", false),
+    ('<', "&lt;", false),
+    ('>', "&gt;", false),
+    ('"', "&quot;", true),
+];
+
+/// Escapes `s` to be used as text or as attribute value (in double quotes).
+/// This does the same as `domsl::ssr::Escaper`, but at compile time.
+fn escape(s: &str, attribute: bool) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match ESCAPES.iter().find(|&&(e, _, attr_only)| e == c && (attribute || !attr_only)) {
+            Some((_, replacement, _)) => out.push_str(replacement),
+            None => out.push(c),
+        }
+    }
+    out
+}
+
+/// Generates the tables used by `domsl::ssr` at runtime, so that they are
+/// derived from the same data as the code generated by `html!`.
+pub(crate) fn gen_tables() -> TokenStream {
+    let html_tags = || html::TAG_INFOS.iter();
+    let void = html_tags().filter(|info| info.is_void()).map(|info| &*info.name);
+    let raw_text = html_tags().filter(|info| info.has_raw_text()).map(|info| &*info.name);
+    let escapes = ESCAPES.iter().map(|(c, replacement, attr_only)| {
+        quote! { (#c, #replacement, #attr_only) }
+    });
+
+    quote! {
+        /// All void elements, i.e. elements without end tag.
+        const VOID_ELEMENTS: &[&str] = &[ #( #void ),* ];
+
+        /// All elements whose content is raw text and not HTML.
+        const RAW_TEXT_ELEMENTS: &[&str] = &[ #( #raw_text ),* ];
+
+        /// The replacements done by `Escaper`, see `ESCAPES` in `domsl-macro`.
+        const ESCAPES: &[(char, &str, bool)] = &[ #( #escapes ),* ];
+    }
+}
+
+/// Collects the generated code. Adjacent static strings are merged into one
+/// `push_str` call.
+#[derive(Default)]
+struct Output {
+    code: TokenStream,
+    pending: String,
+}
+
+impl Output {
+    fn push_static(&mut self, s: &str) {
+        self.pending.push_str(s);
+    }
+
+    fn push_code(&mut self, code: TokenStream) {
+        self.flush();
+        self.code.extend(code);
+    }
+
+    fn flush(&mut self) {
+        if !self.pending.is_empty() {
+            let s = mem::take(&mut self.pending);
+            self.code.extend(quote! { #BUF_IDENT.push_str(#s); });
+        }
+    }
+
+    fn finish(mut self) -> TokenStream {
+        self.flush();
+        self.code
+    }
+}
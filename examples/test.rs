@@ -1,6 +1,6 @@
 #![feature(proc_macro_hygiene)]
 
-use domsl::{jsx, Component};
+use domsl::{jsx, Component, Props};
 use web_sys::{Document, Node};
 
 fn main() {
@@ -12,6 +12,7 @@ fn main() {
     });
 }
 
+#[derive(Props)]
 struct Foo {
     color: &'static str,
 }
@@ -19,7 +20,7 @@ impl Component for Foo {
     type Node = web_sys::HtmlParagraphElement;
     fn render(self, document: &Document, children: Vec<Node>) -> Self::Node {
         jsx!(document => {
-            <p style={ format!("color: {}", self.color) }>{ children }</p>
+            <p style:color={ self.color }>{ children }</p>
         })
     }
 }
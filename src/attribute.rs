@@ -0,0 +1,346 @@
+//! Converting values to attribute values.
+//!
+//! All attribute values that are not string literals (e.g. `id={ self.id }`)
+//! are converted with [`AttributeValue`]. Apart from strings and numbers,
+//! this is implemented for `bool` and `Option<T>`. `None` removes the
+//! attribute entirely. How `bool` is converted depends on the attribute: for
+//! boolean attributes of the HTML standard (like `disabled`, `checked` or
+//! `hidden`), `true` sets the attribute to the empty string and `false`
+//! removes it. All other attributes are set to `"true"` or `"false"`, as
+//! expected by attributes like `aria-pressed` or `draggable`. Which
+//! attributes are boolean is decided at compile time.
+//!
+//! ```ignore
+//! let loading = true;
+//! let title: Option<&str> = None;
+//! jsx!(document => {
+//!     <button disabled={ loading } title={ title } aria-busy={ loading }>"Save"</button>
+//! });
+//! // results in `<button disabled="" aria-busy="true">Save</button>`
+//! ```
+//!
+//! Other types that implement `Display` can be used by calling `to_string()`
+//! on them or by implementing `AttributeValue` for them.
+//!
+//! # Classes
+//!
+//! Apart from strings, `class` also accepts iterators of class names (e.g. a
+//! `Vec<&str>`). Single classes can be toggled with `class:name={condition}`,
+//! which can be combined with `class`:
+//!
+//! ```ignore
+//! let active = true;
+//! let extra = vec!["big", "red"];
+//! jsx!(document => {
+//!     <button class="btn" class={ &extra } class:active={ active } class:disabled={ false } />
+//! });
+//! // results in `<button class="btn big red active"></button>`
+//! ```
+//!
+//! The class string is built once, in the order the classes appear in the
+//! markup. If there are no classes at all, the `class` attribute is not set.
+//!
+//! # Styles
+//!
+//! Single CSS properties can be set with `style:property={value}`, which can
+//! be combined with `style`. The values are converted with `AttributeValue`,
+//! so `None` leaves out the property:
+//!
+//! ```ignore
+//! let color = "red";
+//! let width: Option<u32> = None;
+//! jsx!(document => {
+//!     <p style="margin: 0;" style:color={ color } style:font-size="12px" style:width={ width }>
+//!         "Warning"
+//!     </p>
+//! });
+//! // results in `<p style="margin: 0; color: red; font-size: 12px">Warning</p>`
+//! ```
+//!
+//! The property names are checked at compile time against a list of all
+//! standard CSS properties. Like classes, the style string is built once, in
+//! the order the declarations appear in the markup, and the `style`
+//! attribute is not set if it would be empty.
+//!
+//! # Spread attributes
+//!
+//! Attributes that are only known at runtime can be set with `{..attrs}`,
+//! where `attrs` is anything implementing `IntoIterator<Item = (K, V)>` with
+//! `K: AsRef<str>` and `V: AttributeValue`, e.g. a `Vec<(&str, String)>` or a
+//! `HashMap<String, String>`:
+//!
+//! ```ignore
+//! let attrs = vec![("id", "main"), ("class", "spread")];
+//! jsx!(document => { <div class="fixed" {..attrs} /> });
+//! // results in `<div id="main" class="fixed"></div>`
+//! ```
+//!
+//! Unlike the other attributes, spread attributes are not checked at compile
+//! time. Attributes given explicitly take precedence over spread attributes
+//! with the same name. Attributes with invalid names (everything that's not
+//! an XML name, e.g. names with whitespace or `>` or starting with a digit)
+//! are skipped. A warning about them is passed to the handler set with
+//! [`set_warning_handler`]; without one, they are skipped silently:
+//!
+//! ```ignore
+//! domsl::attribute::set_warning_handler(|msg| web_sys::console::warn_1(&msg.into()));
+//! ```
+
+use std::{
+    borrow::Cow,
+    fmt::Write,
+    rc::Rc,
+    sync::{Arc, RwLock},
+};
+
+
+/// Types that can be used as value of an attribute. See [the module
+/// documentation](self) for more information.
+pub trait AttributeValue {
+    /// Returns the string value of the attribute or `None` if the attribute
+    /// should not be set at all. Types that need to be formatted first can
+    /// use `buf` for that. `buf` is empty when this method is called.
+    fn attribute_value<'a>(&'a self, buf: &'a mut String) -> Option<&'a str>;
+
+    /// Like `attribute_value`, but for boolean attributes like `disabled`.
+    /// Only `bool` (and types wrapping it) behave differently here.
+    fn boolean_attribute_value<'a>(&'a self, buf: &'a mut String) -> Option<&'a str> {
+        self.attribute_value(buf)
+    }
+}
+
+/// Results in `"true"` or `"false"`, except for boolean attributes (like
+/// `disabled`): there, `true` sets the attribute to the empty string and
+/// `false` removes it.
+impl AttributeValue for bool {
+    fn attribute_value<'a>(&'a self, _: &'a mut String) -> Option<&'a str> {
+        Some(if *self { "true" } else { "false" })
+    }
+
+    fn boolean_attribute_value<'a>(&'a self, _: &'a mut String) -> Option<&'a str> {
+        if *self { Some("") } else { None }
+    }
+}
+
+/// `None` removes the attribute.
+impl<T: AttributeValue> AttributeValue for Option<T> {
+    fn attribute_value<'a>(&'a self, buf: &'a mut String) -> Option<&'a str> {
+        self.as_ref()?.attribute_value(buf)
+    }
+
+    fn boolean_attribute_value<'a>(&'a self, buf: &'a mut String) -> Option<&'a str> {
+        self.as_ref()?.boolean_attribute_value(buf)
+    }
+}
+
+impl<T: AttributeValue + ?Sized> AttributeValue for &T {
+    fn attribute_value<'a>(&'a self, buf: &'a mut String) -> Option<&'a str> {
+        (**self).attribute_value(buf)
+    }
+
+    fn boolean_attribute_value<'a>(&'a self, buf: &'a mut String) -> Option<&'a str> {
+        (**self).boolean_attribute_value(buf)
+    }
+}
+
+macro_rules! impl_for_str {
+    ($($ty:ty),*) => {
+        $(
+            impl AttributeValue for $ty {
+                fn attribute_value<'a>(&'a self, _: &'a mut String) -> Option<&'a str> {
+                    Some(self)
+                }
+            }
+        )*
+    }
+}
+
+impl_for_str!(str, String, Cow<'_, str>, Box<str>, Rc<str>, Arc<str>);
+
+macro_rules! impl_for_display {
+    ($($ty:ty),*) => {
+        $(
+            impl AttributeValue for $ty {
+                fn attribute_value<'a>(&'a self, buf: &'a mut String) -> Option<&'a str> {
+                    // Writing into a string never fails.
+                    write!(buf, "{}", self).unwrap();
+                    Some(buf)
+                }
+            }
+        )*
+    }
+}
+
+impl_for_display!(
+    i8, i16, i32, i64, i128, isize,
+    u8, u16, u32, u64, u128, usize,
+    f32, f64, char
+);
+
+
+/// Collects the classes for the `class` attribute if it has to be built at
+/// runtime. Used by the generated code.
+#[doc(hidden)]
+#[derive(Debug, Default)]
+pub struct Classes(String);
+
+impl Classes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds all (whitespace separated) classes in `classes`.
+    pub fn add(&mut self, classes: &str) {
+        for class in classes.split_whitespace() {
+            if !self.0.is_empty() {
+                self.0.push(' ');
+            }
+            self.0.push_str(class);
+        }
+    }
+
+    /// Adds `class` if `enabled` is true.
+    pub fn toggle(&mut self, class: &str, enabled: bool) {
+        if enabled {
+            self.add(class);
+        }
+    }
+
+    /// Returns the value of the `class` attribute or `None` if there are no
+    /// classes.
+    pub fn value(&self) -> Option<&str> {
+        if self.0.is_empty() { None } else { Some(&self.0) }
+    }
+}
+
+/// Collects the declarations for the `style` attribute if it has to be built
+/// at runtime. Used by the generated code.
+#[doc(hidden)]
+#[derive(Debug, Default)]
+pub struct Styles(String);
+
+impl Styles {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the declarations in `declarations` (the value of a `style`
+    /// attribute), unless it's `None`.
+    pub fn add<V: AttributeValue>(&mut self, declarations: V) {
+        let mut buf = String::new();
+        if let Some(declarations) = declarations.attribute_value(&mut buf) {
+            self.push(declarations);
+        }
+    }
+
+    /// Adds the declaration `property: value`, unless `value` is `None`.
+    pub fn set<V: AttributeValue>(&mut self, property: &str, value: V) {
+        let mut buf = String::new();
+        if let Some(value) = value.attribute_value(&mut buf) {
+            self.push(&format!("{}: {}", property, value));
+        }
+    }
+
+    /// Returns the value of the `style` attribute or `None` if there are no
+    /// declarations.
+    pub fn value(&self) -> Option<&str> {
+        if self.0.is_empty() { None } else { Some(&self.0) }
+    }
+
+    fn push(&mut self, declarations: &str) {
+        let declarations = declarations.trim().trim_end_matches(';').trim_end();
+        if declarations.is_empty() {
+            return;
+        }
+
+        if !self.0.is_empty() {
+            self.0.push_str("; ");
+        }
+        self.0.push_str(declarations);
+    }
+}
+
+/// Calls `set` for each attribute of a spread (`{..attrs}`) that should be
+/// set, skipping those in `explicit` (the names of the attributes given
+/// explicitly on the same element). `boolean` are the names of the boolean
+/// attributes of the element. Used by the generated code.
+#[doc(hidden)]
+pub fn spread<I, K, V>(
+    attrs: I,
+    explicit: &[&str],
+    boolean: &[&str],
+    mut set: impl FnMut(&str, &str),
+)
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: AsRef<str>,
+    V: AttributeValue,
+{
+    let mut buf = String::new();
+    for (name, value) in attrs {
+        let name = name.as_ref();
+        if !is_valid_name(name) {
+            warn(&format!("skipping invalid attribute name {:?} in spread attributes", name));
+            continue;
+        }
+        if explicit.contains(&name) {
+            continue;
+        }
+
+        buf.clear();
+        let value = if boolean.contains(&name) {
+            value.boolean_attribute_value(&mut buf)
+        } else {
+            value.attribute_value(&mut buf)
+        };
+        if let Some(value) = value {
+            set(name, value);
+        }
+    }
+}
+
+static WARNING_HANDLER: RwLock<Option<fn(&str)>> = RwLock::new(None);
+
+/// Sets the function that is called with warnings about attributes that
+/// can't be set, e.g. spread attributes with invalid names. By default,
+/// these warnings are dropped.
+pub fn set_warning_handler(handler: fn(&str)) {
+    *WARNING_HANDLER.write().unwrap_or_else(|e| e.into_inner()) = Some(handler);
+}
+
+/// Passes `msg` to the warning handler, if there is one.
+pub(crate) fn warn(msg: &str) {
+    let handler = *WARNING_HANDLER.read().unwrap_or_else(|e| e.into_inner());
+    if let Some(handler) = handler {
+        handler(msg);
+    }
+}
+
+/// Returns whether `name` is a valid attribute name for the DOM, i.e. an XML
+/// `Name`. That's stricter than the HTML syntax, but `setAttribute` throws
+/// for everything else.
+fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) if is_name_start_char(first) => chars.all(is_name_char),
+        _ => false,
+    }
+}
+
+/// `NameStartChar` of the XML specification.
+fn is_name_start_char(c: char) -> bool {
+    matches!(c,
+        ':' | 'A'..='Z' | '_' | 'a'..='z' | '\u{C0}'..='\u{D6}' | '\u{D8}'..='\u{F6}'
+        | '\u{F8}'..='\u{2FF}' | '\u{370}'..='\u{37D}' | '\u{37F}'..='\u{1FFF}'
+        | '\u{200C}'..='\u{200D}' | '\u{2070}'..='\u{218F}' | '\u{2C00}'..='\u{2FEF}'
+        | '\u{3001}'..='\u{D7FF}' | '\u{F900}'..='\u{FDCF}' | '\u{FDF0}'..='\u{FFFD}'
+        | '\u{10000}'..='\u{EFFFF}'
+    )
+}
+
+/// `NameChar` of the XML specification.
+fn is_name_char(c: char) -> bool {
+    is_name_start_char(c) || matches!(c,
+        '-' | '.' | '0'..='9' | '\u{B7}' | '\u{300}'..='\u{36F}' | '\u{203F}'..='\u{2040}'
+    )
+}
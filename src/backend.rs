@@ -0,0 +1,285 @@
+//! The abstraction over the thing that creates the nodes.
+//!
+//! The code generated by `jsx!` doesn't call `web_sys` functions directly, but
+//! only methods of the [`Backend`] trait. The expression before the `=>` in
+//! `jsx!(document => { ... })` determines the backend. Usually, that's a
+//! `web_sys::Document` which creates real DOM nodes. But there are other
+//! backends, like [`ssr::Renderer`][crate::ssr::Renderer] which creates nodes
+//! that can be rendered to an HTML string.
+//!
+//! Components can be generic over the backend, so that the same component can
+//! be used with every backend:
+//!
+//! ```ignore
+//! #[derive(Props)]
+//! struct Greeting {
+//!     name: String,
+//! }
+//!
+//! impl<B: Backend> Component<B> for Greeting {
+//!     type Node = B::Element<web_sys::HtmlParagraphElement>;
+//!     fn render(self, backend: &B, children: Vec<B::Node>) -> Self::Node {
+//!         jsx!(backend => { <p>"Hello "{ self.name }{ children }</p> })
+//!     }
+//! }
+//! ```
+//!
+//! Function components (`#[domsl::component]`) are generic over the backend if
+//! they have a parameter `#[domsl(backend)] backend: &B`, where `B` is a type
+//! parameter of the function. Without it, they only work with
+//! `web_sys::Document`:
+//!
+//! ```ignore
+//! #[domsl::component]
+//! fn greeting<B: Backend>(
+//!     #[domsl(backend)] backend: &B,
+//!     name: String,
+//!     #[domsl(children)] children: Vec<B::Node>,
+//! ) -> B::Element<web_sys::HtmlParagraphElement> {
+//!     jsx!(backend => { <p>"Hello "{ name }{ children }</p> })
+//! }
+//! ```
+
+use wasm_bindgen::JsCast;
+use web_sys::{Document, Element, Node, ShadowRootInit, ShadowRootMode};
+
+use crate::event::Event;
+
+
+/// Something that can create and connect nodes. See [the module
+/// documentation](self) for more information.
+///
+/// All methods may assume that they are called in a way that makes sense for
+/// HTML: `set_attribute` and `add_event_listener` are only called with
+/// elements, attribute and tag names only contain valid characters, and so
+/// on. The code generated by `jsx!` makes sure that's the case.
+pub trait Backend {
+    /// The type of all nodes (elements, text nodes and fragments).
+    type Node: Clone + AsRef<Self::Node>;
+
+    /// The type `jsx!` evaluates to if the root of the markup is an element.
+    /// `T` is the type of the element in the DOM (e.g. `HtmlDivElement` for
+    /// `<div>`, see [`ElementType`]). Backends that don't have element
+    /// specific types can just use `Self::Node` here.
+    type Element<T: ElementType>: Into<Self::Node>;
+
+    /// Creates an element with the given tag name, e.g. `div`.
+    fn create_element(&self, tag: &str) -> Self::Node;
+
+    /// Creates an element with the given namespace URI and tag name. This is
+    /// used for all elements not in the HTML namespace, e.g. `circle` in the
+    /// namespace `http://www.w3.org/2000/svg`.
+    fn create_element_ns(&self, namespace: &str, tag: &str) -> Self::Node;
+
+    /// Creates a text node with the given content.
+    fn create_text(&self, text: &str) -> Self::Node;
+
+    /// Creates an empty fragment. When appending a fragment to another node,
+    /// the fragment's children are moved into that node.
+    fn create_fragment(&self) -> Self::Node;
+
+    /// Sets the attribute `name` of `element` to `value`.
+    fn set_attribute(&self, element: &Self::Node, name: &str, value: &str);
+
+    /// Sets the DOM property `name` of `element` to `value`. `set` is the
+    /// typed setter of that property on the element type `T` (e.g.
+    /// `HtmlInputElement::set_value`) which backends creating real DOM nodes
+    /// call with `element` cast to `T`. Other backends can ignore the
+    /// property or just record it. This is used for `prop:` attributes.
+    fn set_property<T, V>(&self, element: &Self::Node, name: &str, set: fn(&T, V), value: V)
+    where
+        T: ElementType,
+        V: PropertyValue;
+
+    /// Appends `child` as last child to `parent`.
+    fn append_child(&self, parent: &Self::Node, child: &Self::Node);
+
+    /// Attaches a shadow root with the given mode to `host` and returns it.
+    /// Children appended to the returned node end up in the shadow tree.
+    /// This is used for `<#shadow>`.
+    fn attach_shadow(&self, host: &Self::Node, mode: ShadowMode) -> Self::Node;
+
+    /// Registers `listener` for the event `name` on `element`. Backends that
+    /// cannot fire events can just drop the listener. See [`event`][crate::event]
+    /// for more information.
+    fn add_event_listener<E, F>(&self, element: &Self::Node, name: &str, listener: F)
+    where
+        E: Event,
+        F: FnMut(E) + 'static;
+
+    /// Converts the given element node to its specific type.
+    fn cast_element<T: ElementType>(&self, element: Self::Node) -> Self::Element<T>;
+}
+
+/// The mode of a shadow root, see [`Backend::attach_shadow`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShadowMode {
+    /// The shadow root can be accessed from outside (e.g. via
+    /// `Element.shadowRoot` in the DOM).
+    Open,
+    /// The shadow root can't be accessed from outside.
+    Closed,
+}
+
+/// The DOM type of an element, e.g. `web_sys::HtmlDivElement` for `<div>`.
+/// This is the type parameter of [`Backend::Element`] and implemented for
+/// all `web_sys` types representing elements. Backends other than
+/// `web_sys::Document` only use it as a marker.
+pub trait ElementType: Into<Node> + 'static {
+    /// Casts `node` to this type, panicking if it has a different type. Only
+    /// used by the backend creating real DOM nodes.
+    #[doc(hidden)]
+    fn domsl_from_node(node: Node) -> Self;
+
+    /// Like `domsl_from_node`, but without checking the type.
+    #[doc(hidden)]
+    fn domsl_cast_ref(node: &Node) -> &Self;
+}
+
+impl<T: JsCast + Into<Node> + 'static> ElementType for T {
+    fn domsl_from_node(node: Node) -> Self {
+        node.dyn_into().unwrap()
+    }
+
+    fn domsl_cast_ref(node: &Node) -> &Self {
+        node.unchecked_ref()
+    }
+}
+
+/// A value of a DOM property, as passed to [`Backend::set_property`].
+/// Implemented for the types of all property setters in `web_sys` that
+/// `prop:` attributes can use: strings, `bool` and numbers.
+pub trait PropertyValue {
+    /// Returns the value as JS literal, e.g. `"foo"` (with quotes) for a
+    /// string or `true`. Backends that can't set real properties can use this
+    /// to record the value.
+    fn to_js_literal(&self) -> String;
+}
+
+impl PropertyValue for &str {
+    fn to_js_literal(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+impl<T: PropertyValue> PropertyValue for Option<T> {
+    fn to_js_literal(&self) -> String {
+        match self {
+            Some(v) => v.to_js_literal(),
+            None => "null".into(),
+        }
+    }
+}
+
+macro_rules! impl_property_value {
+    ($($ty:ty),*) => {
+        $(
+            impl PropertyValue for $ty {
+                fn to_js_literal(&self) -> String {
+                    self.to_string()
+                }
+            }
+        )*
+    };
+}
+
+impl_property_value!(bool, i8, i16, i32, i64, u8, u16, u32, u64, f32, f64);
+
+impl Backend for Document {
+    type Node = Node;
+    type Element<T: ElementType> = T;
+
+    fn create_element(&self, tag: &str) -> Self::Node {
+        // This only fails if the name contains invalid characters.
+        Document::create_element(self, tag).unwrap().into()
+    }
+
+    fn create_element_ns(&self, namespace: &str, tag: &str) -> Self::Node {
+        // This only fails if the name contains invalid characters.
+        Document::create_element_ns(self, Some(namespace), tag).unwrap().into()
+    }
+
+    fn create_text(&self, text: &str) -> Self::Node {
+        self.create_text_node(text).into()
+    }
+
+    fn create_fragment(&self) -> Self::Node {
+        self.create_document_fragment().into()
+    }
+
+    fn set_attribute(&self, element: &Self::Node, name: &str, value: &str) {
+        if element.unchecked_ref::<Element>().set_attribute(name, value).is_err() {
+            crate::attribute::warn(&format!("failed to set attribute {:?}", name));
+        }
+    }
+
+    fn set_property<T, V>(&self, element: &Self::Node, _: &str, set: fn(&T, V), value: V)
+    where
+        T: ElementType,
+        V: PropertyValue,
+    {
+        set(T::domsl_cast_ref(element), value);
+    }
+
+    fn append_child(&self, parent: &Self::Node, child: &Self::Node) {
+        parent.append_child(child).unwrap();
+    }
+
+    fn attach_shadow(&self, host: &Self::Node, mode: ShadowMode) -> Self::Node {
+        let mode = match mode {
+            ShadowMode::Open => ShadowRootMode::Open,
+            ShadowMode::Closed => ShadowRootMode::Closed,
+        };
+
+        // This only fails if the element already has a shadow root or can't
+        // have one, which `jsx!` already checks.
+        host.unchecked_ref::<Element>().attach_shadow(&ShadowRootInit::new(mode)).unwrap().into()
+    }
+
+    fn add_event_listener<E, F>(&self, element: &Self::Node, name: &str, listener: F)
+    where
+        E: Event,
+        F: FnMut(E) + 'static,
+    {
+        E::domsl_add_listener(element, name, listener);
+    }
+
+    fn cast_element<T: ElementType>(&self, element: Self::Node) -> T {
+        T::domsl_from_node(element)
+    }
+}
+
+
+// ===== Helpers for the generated code ======================================
+
+/// Makes it possible to get a `&B` from a `B`, `&B`, `&&B`, ... via
+/// auto-deref. That way, users can pass both, the backend or a reference to
+/// it, to `jsx!`.
+#[doc(hidden)]
+pub trait AsBackend {
+    fn domsl_as_backend(&self) -> &Self;
+}
+
+impl<B: Backend> AsBackend for B {
+    fn domsl_as_backend(&self) -> &Self {
+        self
+    }
+}
+
+/// Renders `component` with the backend `B`. Taking the backend first makes
+/// sure that the error for components that don't support `B` mentions `B`.
+#[doc(hidden)]
+pub fn render_component<B, C>(backend: &B, component: C, children: Vec<B::Node>) -> B::Node
+where
+    B: Backend,
+    C: crate::Component<B>,
+{
+    component.render(backend, children).into()
+}
+
+/// Converts something into a node of the backend `B`. This mostly exists to
+/// help type inference in the generated code.
+#[doc(hidden)]
+pub fn into_node<B: Backend>(_: &B, node: impl Into<B::Node>) -> B::Node {
+    node.into()
+}
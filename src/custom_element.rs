@@ -0,0 +1,208 @@
+//! Using components as custom elements in non-Rust pages.
+//!
+//! With `#[component(Foo, custom_element = "x-foo")]`, the component struct
+//! gets a `define_custom_element` function. Calling it (e.g. in the start
+//! function of your wasm module) registers `<x-foo>` with
+//! `customElements.define`, so it can be used in any HTML page:
+//!
+//! ```ignore
+//! #[component(Counter, custom_element = "x-counter")]
+//! fn counter(label: String, start: u32, #[domsl(children)] c: Vec<Node>) -> HtmlDivElement {
+//!     let document = web_sys::window().unwrap().document().unwrap();
+//!     jsx!(document => { <div>{ label }": "{ start }{ c }</div> })
+//! }
+//!
+//! Counter::define_custom_element();
+//! // Now `<x-counter label="Clicks" start="3">...</x-counter>` works in JS land.
+//! ```
+//!
+//! Custom elements can't be redefined, so calling `define_custom_element`
+//! again does nothing and returns `false`.
+//!
+//! Every property of the component is an observed attribute. The attribute
+//! name is the property name with `_` replaced by `-` (e.g. `max_length`
+//! becomes `max-length`). The attribute values are converted with
+//! [`FromAttribute`].
+//!
+//! The component is rendered into the element's (open) shadow root when the
+//! element is connected to the document and again each time an attribute
+//! changes. The children of the component are a single `<slot>` element, so
+//! the children of the custom element show up wherever the component places
+//! its children.
+
+use std::str::FromStr;
+
+use wasm_bindgen::{closure::Closure, prelude::*};
+use web_sys::{Document, HtmlElement, Node};
+
+use crate::Component;
+
+
+#[wasm_bindgen(inline_js = "
+    export function domsl_define(name, observed, render) {
+        if (customElements.get(name) !== undefined) {
+            return false;
+        }
+        customElements.define(name, class extends HTMLElement {
+            static get observedAttributes() { return observed; }
+            constructor() {
+                super();
+                this.attachShadow({ mode: 'open' });
+            }
+            connectedCallback() { render(this); }
+            attributeChangedCallback() {
+                if (this.isConnected) {
+                    render(this);
+                }
+            }
+        });
+        return true;
+    }
+")]
+extern "C" {
+    fn domsl_define(name: &str, observed: Box<[JsValue]>, render: &JsValue) -> bool;
+}
+
+/// Registers the custom element `name` which renders the component returned
+/// by `props`. `observed` are the names of the attributes `props` reads. This
+/// is used by the code generated for `custom_element = "..."`; see the module
+/// documentation for more information.
+///
+/// Custom elements can't be redefined, so if `name` is already defined (e.g.
+/// because this is called twice), nothing happens and `false` is returned.
+pub fn define<C, F>(name: &str, observed: &[&str], props: F) -> bool
+where
+    C: Component,
+    F: Fn(&HtmlElement) -> C + 'static,
+{
+    let render = move |host: HtmlElement| {
+        // The shadow root is attached in the constructor of the element.
+        let shadow = host.shadow_root().expect("custom element has no shadow root");
+        let document = host.owner_document().unwrap_or_else(|| {
+            web_sys::window().and_then(|w| w.document()).expect("no document available")
+        });
+
+        let slot: Node = Document::create_element(&document, "slot").unwrap().into();
+        let node = props(&host).render(&document, vec![slot]).into();
+
+        shadow.set_inner_html("");
+        shadow.append_child(&node).unwrap();
+    };
+
+    // The element class references the closure forever, so we can give up
+    // ownership of it.
+    let render = Closure::wrap(Box::new(render) as Box<dyn FnMut(HtmlElement)>);
+    let observed = observed.iter().map(|&name| JsValue::from_str(name)).collect();
+    domsl_define(name, observed, &render.into_js_value())
+}
+
+
+/// Types that props of custom elements can have.
+///
+/// This is the counterpart of [`AttributeValue`][crate::AttributeValue]:
+/// `bool` is `true` if the attribute is present and `Option<T>` is `None` if
+/// it's not. Strings take the value as is and numbers are parsed. If the
+/// attribute is missing or its value is invalid (e.g. not a number), the
+/// default of the prop is used (a warning is logged for invalid values). For
+/// props without default, that's [`FromAttribute::missing`].
+pub trait FromAttribute: Sized {
+    /// Converts the value of an attribute that is set. Returns a message
+    /// describing the problem if the value is invalid.
+    fn from_attribute(value: &str) -> Result<Self, String>;
+
+    /// The value if the attribute is not set and the prop has no default.
+    fn missing() -> Self;
+}
+
+/// Reads the prop from the attribute `attr` of `host`. `default` is called
+/// if the attribute is missing or its value is invalid. This is used by the
+/// code generated for `custom_element = "..."`.
+#[doc(hidden)]
+pub fn read_attribute<T, F>(host: &HtmlElement, attr: &str, default: F) -> T
+where
+    T: FromAttribute,
+    F: FnOnce() -> T,
+{
+    parse_attribute(host.get_attribute(attr).as_deref(), default, |msg| {
+        let msg = format!(
+            "invalid value for attribute `{}` of `<{}>` (using default instead): {}",
+            attr,
+            host.local_name(),
+            msg,
+        );
+        web_sys::console::warn_1(&msg.into());
+    })
+}
+
+/// Converts `value`, the value of an attribute or `None` if it's missing,
+/// into a prop. Like [`read_attribute`], but without the element, so it
+/// doesn't need a browser. `on_invalid` is called with the error message
+/// before falling back to `default` for invalid values.
+#[doc(hidden)]
+pub fn parse_attribute<T, F>(value: Option<&str>, default: F, on_invalid: impl FnOnce(&str)) -> T
+where
+    T: FromAttribute,
+    F: FnOnce() -> T,
+{
+    match value {
+        Some(value) => T::from_attribute(value).unwrap_or_else(|msg| {
+            on_invalid(&msg);
+            default()
+        }),
+        None => default(),
+    }
+}
+
+impl FromAttribute for bool {
+    fn from_attribute(_: &str) -> Result<Self, String> {
+        Ok(true)
+    }
+
+    fn missing() -> Self {
+        false
+    }
+}
+
+impl FromAttribute for String {
+    fn from_attribute(value: &str) -> Result<Self, String> {
+        Ok(value.to_owned())
+    }
+
+    fn missing() -> Self {
+        String::new()
+    }
+}
+
+impl<T: FromAttribute> FromAttribute for Option<T> {
+    fn from_attribute(value: &str) -> Result<Self, String> {
+        T::from_attribute(value).map(Some)
+    }
+
+    fn missing() -> Self {
+        None
+    }
+}
+
+macro_rules! impl_for_from_str {
+    ($($ty:ty),*) => {
+        $(
+            impl FromAttribute for $ty {
+                fn from_attribute(value: &str) -> Result<Self, String> {
+                    <$ty>::from_str(value.trim()).map_err(|e| {
+                        format!("'{}' is not a valid `{}`: {}", value, stringify!($ty), e)
+                    })
+                }
+
+                fn missing() -> Self {
+                    0 as $ty
+                }
+            }
+        )*
+    }
+}
+
+impl_for_from_str!(
+    i8, i16, i32, i64, i128, isize,
+    u8, u16, u32, u64, u128, usize,
+    f32, f64
+);
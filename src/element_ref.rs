@@ -0,0 +1,70 @@
+//! References to elements inside the markup via `ref` attributes.
+//!
+//! `jsx!` only returns the root of the markup. To get a handle to an element
+//! deeper inside, create an [`ElementRef`] beforehand and pass it to the
+//! element's `ref` attribute:
+//!
+//! ```ignore
+//! let name_input = ElementRef::new();
+//! let form = jsx!(document => {
+//!     <form>
+//!         <input ref={name_input} />
+//!         <button on:click={...}>"Submit"</button>
+//!     </form>
+//! });
+//!
+//! // `name_input.get()` returns a `web_sys::HtmlInputElement`.
+//! name_input.get().unwrap().focus().unwrap();
+//! ```
+//!
+//! The type of the element is derived from its tag, just like the type of
+//! the root. More precisely, for the backend `B`, a ref for an `<input>` has
+//! the type `ElementRef<B::Element<HtmlInputElement>>`. The ref is only
+//! borrowed, so it can be used afterwards. Cloning a ref results in a handle
+//! to the same slot, which is useful to use it in event listeners. If the
+//! element is created multiple times (e.g. in a loop), the ref points to the
+//! last one.
+
+use std::{cell::RefCell, fmt, rc::Rc};
+
+
+/// A slot that is filled with an element by `jsx!`. See [the module
+/// documentation](self) for more information.
+pub struct ElementRef<T>(Rc<RefCell<Option<T>>>);
+
+impl<T: Clone> ElementRef<T> {
+    /// Creates an empty ref.
+    pub fn new() -> Self {
+        ElementRef(Rc::new(RefCell::new(None)))
+    }
+
+    /// Returns the element or `None` if no element with this ref was created
+    /// yet.
+    pub fn get(&self) -> Option<T> {
+        self.0.borrow().clone()
+    }
+
+    /// Sets the element, replacing the old one. This is called by the code
+    /// generated for `ref` attributes.
+    pub fn set(&self, element: T) {
+        *self.0.borrow_mut() = Some(element);
+    }
+}
+
+impl<T> Clone for ElementRef<T> {
+    fn clone(&self) -> Self {
+        ElementRef(self.0.clone())
+    }
+}
+
+impl<T: Clone> Default for ElementRef<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ElementRef<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ElementRef").field(&self.0.borrow()).finish()
+    }
+}
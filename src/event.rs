@@ -0,0 +1,67 @@
+//! Event listeners attached via `on:` attributes.
+//!
+//! In `jsx!`, you can attach event listeners to HTML elements by using the
+//! `on:` namespace followed by the event name:
+//!
+//! ```ignore
+//! jsx!(document => {
+//!     <button on:click={move |e| log(e.client_x())}>"Click me"</button>
+//! })
+//! ```
+//!
+//! The value has to be a closure taking the event object as its only
+//! argument. The type of that argument is derived from the event name, e.g.
+//! `web_sys::MouseEvent` for `click` or `web_sys::KeyboardEvent` for
+//! `keydown`. That's why you usually don't need to annotate it. Events unknown
+//! to `domsl` get the general `web_sys::Event`. So does `input`, because it's
+//! only an `InputEvent` for text inputs and not for e.g. `<select>`.
+//!
+//! # Who owns the closure?
+//!
+//! The element does. The closure is converted into a JS function which is
+//! then registered as event listener. After that, Rust doesn't hold on to the
+//! closure anymore and the JS garbage collector takes over: the closure lives
+//! as long as the element (or anything else) references the JS function. This
+//! means the closure has to be `'static`, so you usually want `move`
+//! closures that own (or reference count) the data they need.
+//!
+//! If the platform does not support weak references, the Rust part of the
+//! closure is never freed, i.e. it is leaked. Keep that in mind when creating
+//! lots of short lived elements with event listeners.
+
+use wasm_bindgen::{closure::Closure, convert::FromWasmAbi, JsCast};
+use web_sys::EventTarget;
+
+
+/// The type of an event object passed to a listener, e.g.
+/// `web_sys::MouseEvent`. This is the event type parameter of
+/// [`Backend::add_event_listener`][crate::Backend::add_event_listener] and
+/// implemented for all `web_sys` types that can be passed from JS.
+pub trait Event: Sized + 'static {
+    /// Registers `listener` on a real DOM node via [`add_listener`]. Only
+    /// used by the backend creating real DOM nodes.
+    #[doc(hidden)]
+    fn domsl_add_listener<F: FnMut(Self) + 'static>(target: &EventTarget, name: &str, listener: F);
+}
+
+impl<E: FromWasmAbi + 'static> Event for E {
+    fn domsl_add_listener<F: FnMut(Self) + 'static>(target: &EventTarget, name: &str, listener: F) {
+        add_listener(target, name, listener);
+    }
+}
+
+
+/// Registers `listener` for the event `name` on `target`. This is used by the
+/// code generated for `on:` attributes; see the module documentation for
+/// more information.
+pub fn add_listener<E, F>(target: &EventTarget, name: &str, listener: F)
+where
+    E: FromWasmAbi + 'static,
+    F: FnMut(E) + 'static,
+{
+    let closure = Closure::wrap(Box::new(listener) as Box<dyn FnMut(E)>);
+    let function = closure.into_js_value();
+
+    // This only fails if `function` is not callable, which cannot happen.
+    target.add_event_listener_with_callback(name, function.unchecked_ref()).unwrap();
+}
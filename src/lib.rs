@@ -1,16 +1,66 @@
 #![feature(associated_type_bounds)]
 
-use web_sys::{Document, Node};
+use web_sys::Document;
 
 
+pub mod attribute;
+pub mod backend;
+pub mod custom_element;
+pub mod element_ref;
+pub mod event;
+pub mod mock;
+pub mod props;
 pub mod specialization_hack;
+pub mod ssr;
 
-pub use domsl_macro::{component, jsx};
+pub use crate::{attribute::AttributeValue, backend::Backend, element_ref::ElementRef};
+pub use domsl_macro::{component, html, jsx, Component, Props};
 
 
-pub trait Component {
-    type Node: Into<Node>;
-    fn render(self, document: &Document, children: Vec<Node>) -> Self::Node;
+/// A component that can be used in `jsx!` like `<Foo bar="baz">...</Foo>`.
+///
+/// The type parameter is the backend this component can be rendered with. It
+/// defaults to `web_sys::Document`, but components can be generic over it to
+/// work with all backends (see [`backend`] for an example).
+///
+/// `jsx!` creates components through a builder, which has to be derived with
+/// `#[derive(Props)]` for hand-written components (see [`props`]).
+///
+/// Instead of implementing this trait by hand or using
+/// `#[domsl::component]`, it can be derived for a struct. The builder is
+/// derived as well. The derived impl works with all backends: the component
+/// is rendered with the struct's `render(self, backend: &B, children:
+/// Vec<B::Node>)` method, or with the function given by
+/// `#[domsl(render = path)]`, which has to be generic over the backend `B`.
+/// Its result has to convert into `B::Node`, which is also the `Node` type of
+/// the derived impl.
+///
+/// ```ignore
+/// /// A card showing the name of a user.
+/// #[derive(Clone, Debug, Component)]
+/// pub struct UserCard {
+///     /// The name of the user.
+///     pub name: String,
+///     #[domsl(default)]
+///     pub admin: bool,
+/// }
+///
+/// impl UserCard {
+///     fn render<B: Backend>(self, backend: &B, children: Vec<B::Node>) -> B::Node {
+///         jsx!(backend => {
+///             <div class:admin={ self.admin }>{ self.name }{ children }</div>
+///         }).into()
+///     }
+/// }
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "the component `{Self}` can't be rendered with the backend `{B}`",
+    note = "function components need a `#[domsl(backend)] backend: &B` parameter to work with \
+        all backends (see `domsl::backend`)",
+)]
+pub trait Component<B: Backend = Document> {
+    type Node: Into<B::Node>;
+    fn render(self, backend: &B, children: Vec<B::Node>) -> Self::Node;
 }
 
 
@@ -56,9 +106,10 @@ pub trait Component {
 /// `<div>{ v }</div>` where `v` has the type `T`, the following things are
 /// tried and the first successful one is used:
 ///
-/// - **`T: AsRef<web_sys::Node>`** (*node-like*): this is implemented for lots
-///   of types from `web_sys`, in particular for all types that represent an
-///   HTML element, for `DocumentFragment` and for `Node` itself.
+/// - **`T: AsRef<B::Node>`** (*node-like*) where `B` is the backend: for the
+///   `web_sys::Document` backend, this is implemented for lots of types from
+///   `web_sys`, in particular for all types that represent an HTML element,
+///   for `DocumentFragment` and for `Node` itself.
 /// - **`T: AsRef<str>`** (*string-like*): this is implemented for `&str`,
 ///   `String`, `&String` and a few others. This is just a special case of the
 ///   `Display` case below to avoid one heap allocation in these cases. This
@@ -72,15 +123,15 @@ pub trait Component {
 ///   trick" does not allow "recursion", so to speak. So we can't simply say
 ///   `IntoItertor<Item: IntoNode>`. Instead, one level of recursion is
 ///   manually implemented:
-///   - **`T: IntoIterator<Item: AsRef<web_sys::Node>>`**
+///   - **`T: IntoIterator<Item: AsRef<B::Node>>`**
 ///   - **`T: IntoIterator<Item: AsRef<str>>`**
 ///   - **`T: IntoIterator<Item: Display>`**
 ///   - ... but no nested iterators. In these cases, `Iterator::flat_map` can
 ///     help you out!
 ///
 #[cfg(doc)]
-pub trait IntoNode {
+pub trait IntoNode<B: Backend> {
     /// Creates a DOM node representing `self`. See this trait's documentation
     /// for more information.
-    fn domsl_into_node(&self) -> web_sys::Node;
+    fn domsl_into_node(&self, backend: &B) -> B::Node;
 }
@@ -0,0 +1,429 @@
+//! An in-memory DOM implementation for testing without a browser.
+//!
+//! [`Document`] is a backend for `jsx!` that creates [`Node`]s purely in Rust
+//! memory. These nodes can be inspected with an API resembling the one of the
+//! real DOM (`children`, `text_content`, `inner_html`, ...). This makes it
+//! possible to test markup and components with a normal `cargo test`, without
+//! `wasm-pack` or a browser.
+//!
+//! ```ignore
+//! let d = domsl::mock::Document::new();
+//! let div = jsx!(d => { <div class="foo"><span>"Hello"</span></div> });
+//!
+//! assert_eq!(div.get_attribute("class").as_deref(), Some("foo"));
+//! assert_eq!(div.children()[0].tag_name().as_deref(), Some("span"));
+//! assert_eq!(div.inner_html(), "<span>Hello</span>");
+//! ```
+//!
+//! Components have to implement `Component<mock::Document>` (usually by being
+//! generic over the backend) to be usable with this backend. Event listeners
+//! are accepted, but dropped, as there is no way to fire events. DOM
+//! properties set via `prop:` are recorded and can be read with
+//! [`Node::property`]. Like in the DOM, shadow roots are not part of
+//! `inner_html` and only open ones are returned by `shadow_root`.
+//!
+//! This is also the backend used to render components in `html!`, which is
+//! why it is available as [`ssr::Renderer`][crate::ssr::Renderer] as well.
+//! [`Node::to_html`] renders a node for that use case: unlike `outer_html`,
+//! it includes shadow roots as declarative shadow DOM.
+
+use std::{
+    cell::RefCell,
+    fmt,
+    mem,
+    rc::{Rc, Weak},
+};
+
+
+use crate::{
+    backend::{Backend, ElementType, PropertyValue, ShadowMode},
+    event::Event,
+    ssr::{self, Html},
+};
+
+
+const HTML_NAMESPACE: &str = "http://www.w3.org/1999/xhtml";
+
+
+/// A backend creating in-memory [`Node`]s. See [the module
+/// documentation](self) for more information.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Document;
+
+impl Document {
+    pub fn new() -> Self {
+        Document
+    }
+}
+
+/// A node in the mock DOM: an element, a text node, a fragment or raw HTML
+/// (created from [`Html`]).
+///
+/// Like in the real DOM, a node is a handle: cloning it is cheap and results
+/// in a handle to the same node. Two `Node`s are equal if they are handles to
+/// the same node.
+#[derive(Clone)]
+pub struct Node(Rc<RefCell<NodeData>>);
+
+struct NodeData {
+    kind: NodeKind,
+    parent: Weak<RefCell<NodeData>>,
+}
+
+enum NodeKind {
+    Element {
+        name: String,
+        /// `None` for HTML elements.
+        namespace: Option<String>,
+        attributes: Vec<(String, String)>,
+        /// The properties with their values as JS literals.
+        properties: Vec<(String, String)>,
+        children: Vec<Node>,
+        /// The shadow root (a fragment) and its mode.
+        shadow_root: Option<(ShadowMode, Node)>,
+    },
+    Text(String),
+    Fragment(Vec<Node>),
+    /// HTML that is inserted as is when rendering. There is no such node in
+    /// the real DOM, it only exists for `html!`.
+    Raw(Html),
+}
+
+impl Node {
+    fn new(kind: NodeKind) -> Self {
+        Node(Rc::new(RefCell::new(NodeData { kind, parent: Weak::new() })))
+    }
+
+    /// Returns `true` if this node is an element.
+    pub fn is_element(&self) -> bool {
+        matches!(self.0.borrow().kind, NodeKind::Element { .. })
+    }
+
+    /// Returns `true` if this node is a text node.
+    pub fn is_text(&self) -> bool {
+        matches!(self.0.borrow().kind, NodeKind::Text(_))
+    }
+
+    /// Returns `true` if this node is a fragment.
+    pub fn is_fragment(&self) -> bool {
+        matches!(self.0.borrow().kind, NodeKind::Fragment(_))
+    }
+
+    /// Returns the tag name (e.g. `div`) if this node is an element.
+    pub fn tag_name(&self) -> Option<String> {
+        match &self.0.borrow().kind {
+            NodeKind::Element { name, .. } => Some(name.clone()),
+            _ => None,
+        }
+    }
+
+    /// Returns the namespace URI if this node is an element. Like in the DOM,
+    /// that's `http://www.w3.org/1999/xhtml` for HTML elements.
+    pub fn namespace_uri(&self) -> Option<String> {
+        match &self.0.borrow().kind {
+            NodeKind::Element { namespace: Some(namespace), .. } => Some(namespace.clone()),
+            NodeKind::Element { namespace: None, .. } => Some(HTML_NAMESPACE.into()),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of the attribute `name` if this node is an element
+    /// with such an attribute.
+    pub fn get_attribute(&self, name: &str) -> Option<String> {
+        match &self.0.borrow().kind {
+            NodeKind::Element { attributes, .. } => {
+                attributes.iter().find(|(n, _)| n == name).map(|(_, v)| v.clone())
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the value of the DOM property `name` (set via `prop:`) in its
+    /// representation as JS literal, e.g. `"\"foo\""` for a string or `true`
+    /// (see [`PropertyValue`]).
+    pub fn property(&self, name: &str) -> Option<String> {
+        match &self.0.borrow().kind {
+            NodeKind::Element { properties, .. } => {
+                properties.iter().find(|(n, _)| n == name).map(|(_, v)| v.clone())
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns all attributes as name-value pairs in the order they were
+    /// first set. Empty if this node is not an element.
+    pub fn attributes(&self) -> Vec<(String, String)> {
+        match &self.0.borrow().kind {
+            NodeKind::Element { attributes, .. } => attributes.clone(),
+            _ => vec![],
+        }
+    }
+
+    /// Returns the shadow root if this node is an element with an open
+    /// shadow root. The shadow root is a fragment.
+    pub fn shadow_root(&self) -> Option<Node> {
+        match &self.0.borrow().kind {
+            NodeKind::Element { shadow_root: Some((ShadowMode::Open, root)), .. } => {
+                Some(root.clone())
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the parent node, if this node has one.
+    pub fn parent_node(&self) -> Option<Node> {
+        self.0.borrow().parent.upgrade().map(Node)
+    }
+
+    /// Returns all children (including text nodes).
+    pub fn child_nodes(&self) -> Vec<Node> {
+        match &self.0.borrow().kind {
+            NodeKind::Element { children, .. } | NodeKind::Fragment(children) => {
+                children.clone()
+            }
+            NodeKind::Text(_) | NodeKind::Raw(_) => vec![],
+        }
+    }
+
+    /// Returns all children that are elements.
+    pub fn children(&self) -> Vec<Node> {
+        self.child_nodes().into_iter().filter(|c| c.is_element()).collect()
+    }
+
+    /// Returns the concatenated text of this node and all its descendants.
+    /// Raw HTML is returned as is.
+    pub fn text_content(&self) -> String {
+        match &self.0.borrow().kind {
+            NodeKind::Text(text) => text.clone(),
+            NodeKind::Raw(html) => html.as_str().into(),
+            NodeKind::Element { children, .. } | NodeKind::Fragment(children) => {
+                children.iter().map(|c| c.text_content()).collect()
+            }
+        }
+    }
+
+    /// Returns the HTML of all children of this node.
+    pub fn inner_html(&self) -> String {
+        let mut buf = String::new();
+        let raw_text = match &self.0.borrow().kind {
+            NodeKind::Element { name, namespace: None, .. } => ssr::is_raw_text_element(name),
+            _ => false,
+        };
+        for child in self.child_nodes() {
+            child.write_html_impl(&mut buf, raw_text, false);
+        }
+        buf
+    }
+
+    /// Returns the HTML of this node, including itself.
+    pub fn outer_html(&self) -> String {
+        let mut buf = String::new();
+        self.write_html_impl(&mut buf, false, false);
+        buf
+    }
+
+    /// Renders this node and all its descendants to [`Html`]. Unlike
+    /// `outer_html`, shadow roots are included as declarative shadow DOM, i.e.
+    /// as `<template shadowrootmode="...">` as first child of their host.
+    pub fn to_html(&self) -> Html {
+        let mut buf = String::new();
+        self.write_html(&mut buf);
+        Html::from_raw(buf)
+    }
+
+    /// Like [`to_html`][Node::to_html], but appends the HTML to `buf`.
+    pub fn write_html(&self, buf: &mut String) {
+        self.write_html_impl(buf, false, true);
+    }
+
+    /// `raw_text` is true inside of elements whose content is not HTML
+    /// (`<script>` and `<style>`).
+    fn write_html_impl(&self, buf: &mut String, raw_text: bool, shadow_roots: bool) {
+        match &self.0.borrow().kind {
+            NodeKind::Element { name, namespace, attributes, children, shadow_root, .. } => {
+                let is_html = namespace.is_none();
+                ssr::write_element(buf, name, is_html, attributes, |buf, raw_text| {
+                    if let (true, Some((mode, root))) = (shadow_roots, shadow_root) {
+                        let mode = match mode {
+                            ShadowMode::Open => "open",
+                            ShadowMode::Closed => "closed",
+                        };
+                        buf.push_str("<template shadowrootmode=\"");
+                        buf.push_str(mode);
+                        buf.push_str("\">");
+                        root.write_html_impl(buf, false, shadow_roots);
+                        buf.push_str("</template>");
+                    }
+                    for child in children {
+                        child.write_html_impl(buf, raw_text, shadow_roots);
+                    }
+                });
+            }
+            NodeKind::Text(text) => ssr::write_text(buf, text, raw_text),
+            NodeKind::Fragment(children) => {
+                for child in children {
+                    child.write_html_impl(buf, raw_text, shadow_roots);
+                }
+            }
+            NodeKind::Raw(html) => buf.push_str(html.as_str()),
+        }
+    }
+
+    /// Removes `child` from the children of this node.
+    fn remove_child(&self, child: &Node) {
+        match &mut self.0.borrow_mut().kind {
+            NodeKind::Element { children, .. } | NodeKind::Fragment(children) => {
+                children.retain(|c| c != child);
+            }
+            NodeKind::Text(_) | NodeKind::Raw(_) => {}
+        }
+        child.0.borrow_mut().parent = Weak::new();
+    }
+}
+
+impl PartialEq for Node {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for Node {}
+
+impl AsRef<Node> for Node {
+    fn as_ref(&self) -> &Node {
+        self
+    }
+}
+
+impl From<Html> for Node {
+    fn from(html: Html) -> Self {
+        Node::new(NodeKind::Raw(html))
+    }
+}
+
+impl fmt::Debug for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0.borrow().kind {
+            NodeKind::Fragment(_) => write!(f, "Fragment({:?})", self.inner_html()),
+            _ => write!(f, "Node({:?})", self.outer_html()),
+        }
+    }
+}
+
+impl Backend for Document {
+    type Node = Node;
+    type Element<T: ElementType> = Node;
+
+    fn create_element(&self, tag: &str) -> Self::Node {
+        Node::new(NodeKind::Element {
+            name: tag.into(),
+            namespace: None,
+            attributes: vec![],
+            properties: vec![],
+            children: vec![],
+            shadow_root: None,
+        })
+    }
+
+    fn create_element_ns(&self, namespace: &str, tag: &str) -> Self::Node {
+        Node::new(NodeKind::Element {
+            name: tag.into(),
+            namespace: Some(namespace.into()),
+            attributes: vec![],
+            properties: vec![],
+            children: vec![],
+            shadow_root: None,
+        })
+    }
+
+    fn create_text(&self, text: &str) -> Self::Node {
+        Node::new(NodeKind::Text(text.into()))
+    }
+
+    fn create_fragment(&self) -> Self::Node {
+        Node::new(NodeKind::Fragment(vec![]))
+    }
+
+    fn set_attribute(&self, element: &Self::Node, name: &str, value: &str) {
+        match &mut element.0.borrow_mut().kind {
+            NodeKind::Element { attributes, .. } => {
+                match attributes.iter_mut().find(|(n, _)| n == name) {
+                    Some((_, v)) => *v = value.into(),
+                    None => attributes.push((name.into(), value.into())),
+                }
+            }
+            _ => panic!("called `set_attribute` on a node that is not an element"),
+        }
+    }
+
+    fn set_property<T, V>(&self, element: &Self::Node, name: &str, _: fn(&T, V), value: V)
+    where
+        T: ElementType,
+        V: PropertyValue,
+    {
+        match &mut element.0.borrow_mut().kind {
+            NodeKind::Element { properties, .. } => {
+                let value = value.to_js_literal();
+                match properties.iter_mut().find(|(n, _)| n == name) {
+                    Some((_, v)) => *v = value,
+                    None => properties.push((name.into(), value)),
+                }
+            }
+            _ => panic!("called `set_property` on a node that is not an element"),
+        }
+    }
+
+    fn append_child(&self, parent: &Self::Node, child: &Self::Node) {
+        // Like in the DOM, the children of fragments are moved and nodes that
+        // already have a parent are removed from it first.
+        let new_children = if child.is_fragment() {
+            match &mut child.0.borrow_mut().kind {
+                NodeKind::Fragment(children) => mem::take(children),
+                _ => unreachable!(),
+            }
+        } else {
+            if let Some(old_parent) = child.parent_node() {
+                old_parent.remove_child(child);
+            }
+            vec![child.clone()]
+        };
+
+        for new_child in &new_children {
+            new_child.0.borrow_mut().parent = Rc::downgrade(&parent.0);
+        }
+
+        match &mut parent.0.borrow_mut().kind {
+            NodeKind::Element { children, .. } | NodeKind::Fragment(children) => {
+                children.extend(new_children);
+            }
+            NodeKind::Text(_) | NodeKind::Raw(_) => {
+                panic!("called `append_child` on a node that cannot have children")
+            }
+        }
+    }
+
+    fn attach_shadow(&self, host: &Self::Node, mode: ShadowMode) -> Self::Node {
+        let root = Node::new(NodeKind::Fragment(vec![]));
+        match &mut host.0.borrow_mut().kind {
+            NodeKind::Element { shadow_root: shadow_root @ None, .. } => {
+                *shadow_root = Some((mode, root.clone()));
+            }
+            NodeKind::Element { .. } => panic!("element already has a shadow root"),
+            _ => panic!("called `attach_shadow` on a node that is not an element"),
+        }
+        root
+    }
+
+    fn add_event_listener<E, F>(&self, _: &Self::Node, _: &str, _: F)
+    where
+        E: Event,
+        F: FnMut(E) + 'static,
+    {
+        // There is no way to fire events, so we don't need to keep the
+        // listener.
+    }
+
+    fn cast_element<T: ElementType>(&self, element: Self::Node) -> Node {
+        element
+    }
+}
@@ -0,0 +1,93 @@
+//! Props of components and their default values.
+//!
+//! `jsx!` doesn't create components with a struct literal, but through a
+//! builder which is generated by `#[domsl::component]`,
+//! `#[derive(Component)]` or, for hand-written components, by
+//! `#[derive(Props)]`:
+//!
+//! ```ignore
+//! #[derive(Props)]
+//! struct Greeting {
+//!     name: String,
+//!     #[domsl(default = "Hello".into())]
+//!     greeting: String,
+//!     #[domsl(default)]
+//!     excited: bool,
+//!     title: Option<String>,
+//! }
+//!
+//! impl<B: Backend> Component<B> for Greeting { ... }
+//!
+//! jsx!(document => { <Greeting name={ name } excited={ true } /> });
+//! ```
+//!
+//! This makes it possible to omit props with a default value: if the prop is
+//! not given, `#[domsl(default)]` uses `Default::default()` and
+//! `#[domsl(default = expr)]` evaluates `expr`. Props of type `Option<T>` are
+//! always optional and default to `None`. The same attributes can be used on
+//! the parameters of `#[domsl::component]` functions and on the fields of
+//! structs deriving `Component`.
+//!
+//! The order of the props doesn't matter. Omitting a prop without default
+//! results in a "missing prop `name` for component `Greeting`" error on the
+//! component tag. Unknown props result in a "no method named `name` found"
+//! error on the prop, as the builder has one setter method per prop. Giving a
+//! prop twice is an error as well.
+//!
+//! Components without builder (e.g. with a hand-written `impl Component`
+//! written before builders existed) can't be used in `jsx!`. That results in
+//! an error on the component tag that suggests `#[derive(Props)]`.
+
+/// Implemented for components by `#[derive(Props)]`, `#[domsl::component]`
+/// and `#[derive(Component)]`. Gives access to the builder through which
+/// `jsx!` creates the component. This trait shouldn't be implemented by
+/// hand.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` has no props builder and can't be used in `jsx!` or `html!`",
+    label = "`{Self}` doesn't implement `domsl::props::Props`",
+    note = "hand-written components need `#[derive(Props)]` (`#[domsl::component]` and \
+        `#[derive(Component)]` derive it automatically)",
+)]
+pub trait Props {
+    #[doc(hidden)]
+    type Builder;
+
+    #[doc(hidden)]
+    fn __domsl_props() -> Self::Builder;
+}
+
+/// Returns the builder of the component `C`. `C` is given as the parameter
+/// type of `pattern` (`|Foo { .. }| ()`), as that also works for components
+/// with generic parameters. Used by the generated code.
+#[doc(hidden)]
+pub fn builder<C: Props>(pattern: impl FnOnce(C)) -> C::Builder {
+    let _ = pattern;
+    C::__domsl_props()
+}
+
+/// The state of a prop that was not given (yet). Used by the generated code.
+#[doc(hidden)]
+pub struct Unset;
+
+/// The state of a prop with the given value. Used by the generated code.
+#[doc(hidden)]
+pub struct Set<T>(pub T);
+
+/// The possible states of an optional prop. Used by the generated code.
+#[doc(hidden)]
+pub trait Optional<T> {
+    /// Returns the value of the prop or `default()` if it was not given.
+    fn or_else(self, default: impl FnOnce() -> T) -> T;
+}
+
+impl<T> Optional<T> for Unset {
+    fn or_else(self, default: impl FnOnce() -> T) -> T {
+        default()
+    }
+}
+
+impl<T> Optional<T> for Set<T> {
+    fn or_else(self, _: impl FnOnce() -> T) -> T {
+        self.0
+    }
+}
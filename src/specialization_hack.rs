@@ -12,23 +12,29 @@
 //!
 //! [1]: https://github.com/dtolnay/case-studies/tree/master/autoref-specialization
 
-use std::fmt::Display;
+use std::fmt::{Display, Write};
 
-use web_sys::{Document, Node};
+use crate::{
+    attribute::{AttributeValue, Classes},
+    backend::Backend,
+    ssr::{Escaper, Html},
+};
 
 
-pub struct Wrap<'a, T>(pub &'a T);
+/// Wraps the value to convert and the backend. The latter is only there so
+/// that the type of the backend is known.
+pub struct Wrap<'a, T, B>(pub &'a T, pub &'a B);
 
-// ===== Implementation for `T: Into<Node>` ==================================
+// ===== Implementation for `T: AsRef<B::Node>` ================================
 //
-// This is the top priority: if a type implements `Into<Node>`, this "impl" is
-// taken, regardless of whether it also implements `AsRef<str>`, `Display` or
-// `IntoIterator`.
+// This is the top priority: if a type implements `AsRef<B::Node>`, this "impl"
+// is taken, regardless of whether it also implements `AsRef<str>`, `Display`
+// or `IntoIterator`.
 
 pub struct NodeTag;
 
 impl NodeTag {
-    pub fn into_node<T: AsRef<Node>>(self, t: T, _: &Document) -> Node {
+    pub fn into_node<B: Backend>(self, t: impl AsRef<B::Node>, _: &B) -> B::Node {
         t.as_ref().clone()
     }
 }
@@ -39,7 +45,7 @@ pub trait NodeKind {
     }
 }
 
-impl<T: AsRef<Node>> NodeKind for &&&&&Wrap<'_, T> {}
+impl<T: AsRef<B::Node>, B: Backend> NodeKind for &&&&&Wrap<'_, T, B> {}
 
 
 // ===== Second priority: `T: AsRef<str>` ==================================
@@ -47,8 +53,8 @@ impl<T: AsRef<Node>> NodeKind for &&&&&Wrap<'_, T> {}
 pub struct AsStrTag;
 
 impl AsStrTag {
-    pub fn into_node(self, x: impl AsRef<str>, document: &Document) -> Node {
-        document.create_text_node(x.as_ref()).into()
+    pub fn into_node<B: Backend>(self, x: impl AsRef<str>, backend: &B) -> B::Node {
+        backend.create_text(x.as_ref())
     }
 }
 
@@ -58,7 +64,7 @@ pub trait AsStrKind {
     }
 }
 
-impl<T: AsRef<str>> AsStrKind for &&&&Wrap<'_, T> {}
+impl<T: AsRef<str>, B: Backend> AsStrKind for &&&&Wrap<'_, T, B> {}
 
 
 // ===== Third priority: `T: Display` ==================================
@@ -66,8 +72,8 @@ impl<T: AsRef<str>> AsStrKind for &&&&Wrap<'_, T> {}
 pub struct DisplayTag;
 
 impl DisplayTag {
-    pub fn into_node(self, x: impl Display, document: &Document) -> Node {
-        document.create_text_node(&x.to_string()).into()
+    pub fn into_node<B: Backend>(self, x: impl Display, backend: &B) -> B::Node {
+        backend.create_text(&x.to_string())
     }
 }
 
@@ -78,26 +84,26 @@ pub trait DisplayKind {
 }
 
 // One `&` more for second priority
-impl<T: Display> DisplayKind for &&&Wrap<'_, T> {}
+impl<T: Display, B: Backend> DisplayKind for &&&Wrap<'_, T, B> {}
 
 
 
 
-// ===== `T: IntoIterator<Item: AsRef<Node>>` ===============
+// ===== `T: IntoIterator<Item: AsRef<B::Node>>` ===============
 
 pub struct IterNodeTag;
 
 impl IterNodeTag {
-    pub fn into_node(
+    pub fn into_node<B: Backend>(
         self,
-        iter: impl IntoIterator<Item: AsRef<Node>>,
-        document: &Document,
-    ) -> Node {
-        let frag = document.create_document_fragment();
+        iter: impl IntoIterator<Item: AsRef<B::Node>>,
+        backend: &B,
+    ) -> B::Node {
+        let frag = backend.create_fragment();
         for e in iter {
-            frag.append_child(&e.as_ref()).unwrap();
+            backend.append_child(&frag, e.as_ref());
         }
-        frag.into()
+        frag
     }
 }
 
@@ -107,24 +113,24 @@ pub trait IterNodeKind {
     }
 }
 
-impl<T: IntoIterator<Item: AsRef<Node>>> IterNodeKind for &&Wrap<'_, T> {}
+impl<T: IntoIterator<Item: AsRef<B::Node>>, B: Backend> IterNodeKind for &&Wrap<'_, T, B> {}
 
 
-// ===== `T: IntoIterator<Item: AsRef<Node>>` ===============
+// ===== `T: IntoIterator<Item: AsRef<str>>` ===============
 pub struct IterStrTag;
 
 impl IterStrTag {
-    pub fn into_node(
+    pub fn into_node<B: Backend>(
         self,
         iter: impl IntoIterator<Item: AsRef<str>>,
-        document: &Document,
-    ) -> Node {
-        let frag = document.create_document_fragment();
+        backend: &B,
+    ) -> B::Node {
+        let frag = backend.create_fragment();
         for e in iter {
-            let t = document.create_text_node(e.as_ref());
-            frag.append_child(&t).unwrap();
+            let t = backend.create_text(e.as_ref());
+            backend.append_child(&frag, &t);
         }
-        frag.into()
+        frag
     }
 }
 
@@ -134,25 +140,25 @@ pub trait IterStrKind {
     }
 }
 
-impl<T: IntoIterator<Item: AsRef<str>>> IterStrKind for &Wrap<'_, T> {}
+impl<T: IntoIterator<Item: AsRef<str>>, B: Backend> IterStrKind for &Wrap<'_, T, B> {}
 
 
 // ===== `T: IntoIterator<Item: Display>` ===============
 pub struct IterDisplayTag;
 
 impl IterDisplayTag {
-    pub fn into_node(
+    pub fn into_node<B: Backend>(
         self,
         iter: impl IntoIterator<Item: Display>,
-        document: &Document,
-    ) -> Node {
-        let frag = document.create_document_fragment();
+        backend: &B,
+    ) -> B::Node {
+        let frag = backend.create_fragment();
         for e in iter {
             // TODO: maybe reuse string buffer
-            let t = document.create_text_node(&e.to_string());
-            frag.append_child(&t).unwrap();
+            let t = backend.create_text(&e.to_string());
+            backend.append_child(&frag, &t);
         }
-        frag.into()
+        frag
     }
 }
 
@@ -162,7 +168,209 @@ pub trait IterDisplayKind {
     }
 }
 
-impl<T: IntoIterator<Item: Display>> IterDisplayKind for Wrap<'_, T> {}
+impl<T: IntoIterator<Item: Display>, B: Backend> IterDisplayKind for Wrap<'_, T, B> {}
+
+
+// ===========================================================================
+// ===== Rendering to HTML strings
+// ===========================================================================
+//
+// The same trick again, but for `html!` which writes values into a string
+// instead of creating DOM nodes. The method has a different name to avoid
+// conflicts with the traits above. The priorities are the same, with `Html`
+// taking the place of `Node`.
+
+pub struct HtmlWrap<'a, T>(pub &'a T);
+
+// ===== `T: AsRef<Html>` ===============
+
+pub struct RawHtmlTag;
+
+impl RawHtmlTag {
+    pub fn write_html(self, x: impl AsRef<Html>, buf: &mut String, _raw_text: bool) {
+        buf.push_str(x.as_ref().as_str());
+    }
+}
+
+pub trait RawHtmlKind {
+    fn domsl_html_kind(&self) -> RawHtmlTag {
+        RawHtmlTag
+    }
+}
+
+impl<T: AsRef<Html>> RawHtmlKind for &&&&&HtmlWrap<'_, T> {}
+
+
+// ===== `T: AsRef<str>` ===============
+
+pub struct EscapeStrTag;
+
+impl EscapeStrTag {
+    pub fn write_html(self, x: impl AsRef<str>, buf: &mut String, raw_text: bool) {
+        // Writing into a string never fails.
+        Escaper::content(buf, raw_text).write_str(x.as_ref()).unwrap();
+    }
+}
+
+pub trait EscapeStrKind {
+    fn domsl_html_kind(&self) -> EscapeStrTag {
+        EscapeStrTag
+    }
+}
+
+impl<T: AsRef<str>> EscapeStrKind for &&&&HtmlWrap<'_, T> {}
+
+
+// ===== `T: Display` ===============
+
+pub struct EscapeDisplayTag;
+
+impl EscapeDisplayTag {
+    pub fn write_html(self, x: impl Display, buf: &mut String, raw_text: bool) {
+        write!(Escaper::content(buf, raw_text), "{}", x).unwrap();
+    }
+}
+
+pub trait EscapeDisplayKind {
+    fn domsl_html_kind(&self) -> EscapeDisplayTag {
+        EscapeDisplayTag
+    }
+}
+
+impl<T: Display> EscapeDisplayKind for &&&HtmlWrap<'_, T> {}
+
+
+// ===== `T: IntoIterator<Item: AsRef<Html>>` ===============
+
+pub struct IterRawHtmlTag;
+
+impl IterRawHtmlTag {
+    pub fn write_html(
+        self,
+        iter: impl IntoIterator<Item: AsRef<Html>>,
+        buf: &mut String,
+        _raw_text: bool,
+    ) {
+        for e in iter {
+            buf.push_str(e.as_ref().as_str());
+        }
+    }
+}
+
+pub trait IterRawHtmlKind {
+    fn domsl_html_kind(&self) -> IterRawHtmlTag {
+        IterRawHtmlTag
+    }
+}
+
+impl<T: IntoIterator<Item: AsRef<Html>>> IterRawHtmlKind for &&HtmlWrap<'_, T> {}
+
+
+// ===== `T: IntoIterator<Item: AsRef<str>>` ===============
+
+pub struct IterEscapeStrTag;
+
+impl IterEscapeStrTag {
+    pub fn write_html(
+        self,
+        iter: impl IntoIterator<Item: AsRef<str>>,
+        buf: &mut String,
+        raw_text: bool,
+    ) {
+        for e in iter {
+            Escaper::content(buf, raw_text).write_str(e.as_ref()).unwrap();
+        }
+    }
+}
+
+pub trait IterEscapeStrKind {
+    fn domsl_html_kind(&self) -> IterEscapeStrTag {
+        IterEscapeStrTag
+    }
+}
+
+impl<T: IntoIterator<Item: AsRef<str>>> IterEscapeStrKind for &HtmlWrap<'_, T> {}
+
+
+// ===== `T: IntoIterator<Item: Display>` ===============
+
+pub struct IterEscapeDisplayTag;
+
+impl IterEscapeDisplayTag {
+    pub fn write_html(
+        self,
+        iter: impl IntoIterator<Item: Display>,
+        buf: &mut String,
+        raw_text: bool,
+    ) {
+        for e in iter {
+            write!(Escaper::content(buf, raw_text), "{}", e).unwrap();
+        }
+    }
+}
+
+pub trait IterEscapeDisplayKind {
+    fn domsl_html_kind(&self) -> IterEscapeDisplayTag {
+        IterEscapeDisplayTag
+    }
+}
+
+impl<T: IntoIterator<Item: Display>> IterEscapeDisplayKind for HtmlWrap<'_, T> {}
+
+
+// ===========================================================================
+// ===== Class values
+// ===========================================================================
+//
+// The value of `class={...}` can be a single string (containing any number of
+// classes) or an iterator of class names. The former has priority, which
+// also includes `Option` and the other types implementing `AttributeValue`.
+// The generated code calls `(&ClassWrap(&x)).domsl_class_kind()`, so the
+// impl for `ClassWrap` is found before the one for `&ClassWrap`.
+
+pub struct ClassWrap<'a, T>(pub &'a T);
+
+// ===== `T: AttributeValue` ===============
+
+pub struct ClassValueTag;
+
+impl ClassValueTag {
+    pub fn add_to(self, x: impl AttributeValue, classes: &mut Classes) {
+        let mut buf = String::new();
+        if let Some(value) = x.attribute_value(&mut buf) {
+            classes.add(value);
+        }
+    }
+}
+
+pub trait ClassValueKind {
+    fn domsl_class_kind(&self) -> ClassValueTag {
+        ClassValueTag
+    }
+}
+
+impl<T: AttributeValue> ClassValueKind for ClassWrap<'_, T> {}
+
+
+// ===== `T: IntoIterator<Item: AsRef<str>>` ===============
+
+pub struct ClassIterTag;
+
+impl ClassIterTag {
+    pub fn add_to(self, iter: impl IntoIterator<Item: AsRef<str>>, classes: &mut Classes) {
+        for class in iter {
+            classes.add(class.as_ref());
+        }
+    }
+}
+
+pub trait ClassIterKind {
+    fn domsl_class_kind(&self) -> ClassIterTag {
+        ClassIterTag
+    }
+}
+
+impl<T: IntoIterator<Item: AsRef<str>>> ClassIterKind for &ClassWrap<'_, T> {}
 
 
 
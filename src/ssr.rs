@@ -0,0 +1,187 @@
+//! Rendering markup to HTML strings (server side rendering).
+//!
+//! The [`html!`][crate::html] macro accepts the same markup as `jsx!`, but
+//! instead of creating DOM nodes via `web_sys`, it evaluates to an [`Html`]
+//! string. No browser or JS environment is needed for this, so you can use it
+//! to pre-render pages on a server or to test your markup with a normal
+//! `cargo test`.
+//!
+//! ```ignore
+//! let name = "<Peter>";
+//! let html = html! {
+//!     <p class="greeting">"Hello "{ name }<br />"Bye"</p>
+//! };
+//! assert_eq!(
+//!     html.as_str(),
+//!     r#"<p class="greeting">Hello &lt;Peter&gt;<br>Bye</p>"#,
+//! );
+//! ```
+//!
+//! All text and attribute values are escaped. The only things not escaped are
+//! interpolated `Html` values (which are already valid HTML) and text inside
+//! `<script>` and `<style>`: the content of these elements is not HTML, so
+//! only `</` is written as `<\/` there (see [`Escaper::raw_text`]).
+//! Interpolated values are converted the same way as in `jsx!` (see
+//! [`IntoNode`][crate::IntoNode]), with `Html` taking the place of DOM nodes.
+//! Event listeners (`on:` attributes) are ignored. Shadow roots (`<#shadow>`)
+//! are rendered as declarative shadow DOM, i.e. as a
+//! `<template shadowrootmode="...">` as first child of their host.
+//!
+//! # Components and the `Renderer` backend
+//!
+//! Components used in `html!` are rendered with the [`Renderer`] backend, so
+//! they have to implement `Component<Renderer>` (usually by being generic
+//! over the backend). `Renderer` is the in-memory backend from
+//! [`mock`][crate::mock] under a different name, so there is only one
+//! implementation of the tree. You can also use `Renderer` with `jsx!`
+//! directly and turn the resulting [`Node`] into HTML via [`Node::to_html`].
+
+use std::fmt::{self, Write};
+
+pub use crate::mock::{Document as Renderer, Node};
+
+
+/// A string containing HTML, e.g. created by [`html!`][crate::html].
+///
+/// When interpolating an `Html` value into another `html!` invocation, it is
+/// inserted as is (not escaped).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Html(String);
+
+impl Html {
+    /// Creates an `Html` value from the given string without any escaping.
+    /// Make sure that `raw` is valid HTML and does not contain any untrusted
+    /// input!
+    pub fn from_raw(raw: String) -> Self {
+        Html(raw)
+    }
+
+    /// Returns the HTML as string slice.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the HTML as string.
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl AsRef<Html> for Html {
+    fn as_ref(&self) -> &Html {
+        self
+    }
+}
+
+impl fmt::Display for Html {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+domsl_macro::__ssr_tables!();
+
+/// A writer that escapes everything written to it and appends the result to
+/// the wrapped string. Used for all dynamic content in `html!`.
+pub struct Escaper<'a> {
+    buf: &'a mut String,
+    context: Context,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Context {
+    Text,
+    Attribute,
+    RawText,
+}
+
+impl<'a> Escaper<'a> {
+    /// Creates an escaper for text content.
+    pub fn text(buf: &'a mut String) -> Self {
+        Self { buf, context: Context::Text }
+    }
+
+    /// Creates an escaper for attribute values (in double quotes).
+    pub fn attribute(buf: &'a mut String) -> Self {
+        Self { buf, context: Context::Attribute }
+    }
+
+    /// Creates an escaper for the content of `<script>` and `<style>`. This
+    /// content is not HTML, so nothing is escaped except `</` (written as
+    /// `<\/`), which could otherwise end the element.
+    pub fn raw_text(buf: &'a mut String) -> Self {
+        Self { buf, context: Context::RawText }
+    }
+
+    /// Creates a text escaper, or a raw text escaper if `raw_text` is true.
+    pub fn content(buf: &'a mut String, raw_text: bool) -> Self {
+        if raw_text { Self::raw_text(buf) } else { Self::text(buf) }
+    }
+}
+
+impl Write for Escaper<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            if self.context == Context::RawText {
+                // Looking at the buffer instead of `s` also catches a `</`
+                // that is split across two writes.
+                if c == '/' && self.buf.ends_with('<') {
+                    self.buf.push('\\');
+                }
+                self.buf.push(c);
+                continue;
+            }
+
+            let attribute = self.context == Context::Attribute;
+            match ESCAPES.iter().find(|&&(e, _, attr_only)| e == c && (attribute || !attr_only)) {
+                Some((_, replacement, _)) => self.buf.push_str(replacement),
+                None => self.buf.push(c),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+
+/// Appends the HTML of an element to `buf`. The children are written by
+/// `write_children` which gets passed whether the content of the element is
+/// raw text (not HTML). `is_html` is false for elements of other namespaces
+/// (like SVG), which are never void and never contain raw text. This is used
+/// by `mock`.
+pub(crate) fn write_element(
+    buf: &mut String,
+    name: &str,
+    is_html: bool,
+    attributes: &[(String, String)],
+    write_children: impl FnOnce(&mut String, bool),
+) {
+    // Writing into a string never fails.
+    buf.push('<');
+    buf.push_str(name);
+    for (name, value) in attributes {
+        write!(buf, " {}=\"", name).unwrap();
+        Escaper::attribute(buf).write_str(value).unwrap();
+        buf.push('"');
+    }
+    buf.push('>');
+
+    if is_html && VOID_ELEMENTS.contains(&name) {
+        return;
+    }
+
+    write_children(buf, is_html && is_raw_text_element(name));
+    write!(buf, "</{}>", name).unwrap();
+}
+
+/// Returns whether the content of the HTML element `name` is raw text (not
+/// HTML), i.e. whether it is `<script>` or `<style>`.
+pub(crate) fn is_raw_text_element(name: &str) -> bool {
+    RAW_TEXT_ELEMENTS.contains(&name)
+}
+
+/// Appends the given text to `buf`, escaped as raw text if `raw_text` is
+/// true.
+pub(crate) fn write_text(buf: &mut String, text: &str, raw_text: bool) {
+    Escaper::content(buf, raw_text).write_str(text).unwrap();
+}
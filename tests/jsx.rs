@@ -1,6 +1,6 @@
 #![feature(proc_macro_hygiene)]
 
-use domsl::jsx;
+use domsl::{jsx, Backend};
 use wasm_bindgen::JsCast;
 use wasm_bindgen_test::*;
 use web_sys::Document;
@@ -131,3 +131,251 @@ fn all_into_node() {
     let n = assert_cast!(cc1, HtmlSpanElement);
     assert_only_has_text!(n, "span2");
 }
+
+#[wasm_bindgen_test]
+fn event_listener() {
+    use std::{cell::Cell, rc::Rc};
+
+    let d = doc();
+    let clicks = Rc::new(Cell::new(0));
+    let button: web_sys::HtmlButtonElement = jsx!(d => {
+        <button on:click={
+            let clicks = clicks.clone();
+            move |e| {
+                assert_eq!(e.button(), 0);
+                clicks.set(clicks.get() + 1);
+            }
+        }>"Click me"</button>
+    });
+
+    button.click();
+    button.click();
+    assert_eq!(clicks.get(), 2);
+}
+
+#[wasm_bindgen_test]
+fn svg_namespace() {
+    let d = doc();
+    let svg: web_sys::SvgsvgElement = jsx!(d => {
+        <svg viewBox="0 0 10 10">
+            <circle cx="5" cy="5" r="4" stroke-width="2" />
+            <foreignObject><p>"html"</p></foreignObject>
+        </svg>
+    });
+
+    const SVG: &str = "http://www.w3.org/2000/svg";
+    assert_eq!(svg.namespace_uri().as_deref(), Some(SVG));
+    let circle = svg.first_element_child().unwrap();
+    assert_eq!(circle.namespace_uri().as_deref(), Some(SVG));
+    assert_eq!(circle.get_attribute("stroke-width").as_deref(), Some("2"));
+    let p = svg.last_element_child().unwrap().first_element_child().unwrap();
+    assert_eq!(p.namespace_uri().as_deref(), Some("http://www.w3.org/1999/xhtml"));
+}
+
+#[domsl::component(Greeting, custom_element = "x-greeting")]
+fn greeting(
+    name: String,
+    excited: bool,
+    #[domsl(children)] children: Vec<web_sys::Node>,
+) -> web_sys::HtmlParagraphElement {
+    let d = doc();
+    let mark = if excited { "!" } else { "." };
+    jsx!(d => { <p>"Hello "{ name }{ mark }{ children }</p> })
+}
+
+#[wasm_bindgen_test]
+fn custom_element() {
+    assert!(Greeting::define_custom_element());
+    assert!(!Greeting::define_custom_element());
+
+    let d = doc();
+    let e = d.create_element("x-greeting").unwrap();
+    e.set_attribute("name", "Anna").unwrap();
+    e.set_inner_html("<b>slotted</b>");
+    d.body().unwrap().append_child(&e).unwrap();
+
+    let shadow = e.shadow_root().unwrap();
+    assert_eq!(shadow.inner_html(), "<p>Hello Anna.<slot></slot></p>");
+
+    // Changing an observed attribute renders the component again.
+    e.set_attribute("excited", "").unwrap();
+    assert_eq!(shadow.inner_html(), "<p>Hello Anna!<slot></slot></p>");
+}
+
+#[domsl::component(Stars, custom_element = "x-stars")]
+fn stars(#[domsl(default = 3)] count: u32) -> web_sys::HtmlSpanElement {
+    let d = doc();
+    jsx!(d => { <span>{ "*".repeat(count as usize) }</span> })
+}
+
+#[wasm_bindgen_test]
+fn custom_element_defaults() {
+    Stars::define_custom_element();
+
+    let d = doc();
+    let e = d.create_element("x-stars").unwrap();
+    e.set_attribute("count", "2").unwrap();
+    d.body().unwrap().append_child(&e).unwrap();
+    let shadow = e.shadow_root().unwrap();
+    assert_eq!(shadow.inner_html(), "<span>**</span>");
+
+    // Values that can't be parsed fall back to the default of the prop.
+    e.set_attribute("count", "many").unwrap();
+    assert_eq!(shadow.inner_html(), "<span>***</span>");
+
+    // So do missing attributes.
+    e.set_attribute("count", "1").unwrap();
+    e.remove_attribute("count").unwrap();
+    assert_eq!(shadow.inner_html(), "<span>***</span>");
+
+    let e = d.create_element("x-stars").unwrap();
+    d.body().unwrap().append_child(&e).unwrap();
+    assert_eq!(e.shadow_root().unwrap().inner_html(), "<span>***</span>");
+}
+
+#[domsl::component]
+fn list<'t, T>(title: &'t str, items: &[T]) -> web_sys::HtmlUListElement
+where
+    T: std::fmt::Display,
+{
+    let d = doc();
+    jsx!(d => {
+        <ul title={ title }>
+            {for item in items { <li>{ item }</li> }}
+        </ul>
+    })
+}
+
+#[wasm_bindgen_test]
+fn generic_component() {
+    let d = doc();
+    let numbers = vec![1, 2];
+    let names = ["a".to_string()];
+    let div = jsx!(d => {
+        <div>
+            <List title="Numbers" items={ &numbers } />
+            <List title={ &names[0] } items={ &names[..] } />
+        </div>
+    });
+
+    assert_eq!(
+        div.inner_html(),
+        "<ul title=\"Numbers\"><li>1</li><li>2</li></ul><ul title=\"a\"><li>a</li></ul>",
+    );
+}
+
+#[domsl::component(rename = "Badge")]
+fn info_badge(
+    text: &str,
+    #[domsl(default = "info")] kind: &str,
+    #[domsl(default)] count: u32,
+    title: Option<&str>,
+) -> web_sys::HtmlSpanElement {
+    let d = doc();
+    jsx!(d => { <span class={ kind } title={ title }>{ text }" ("{ count }")"</span> })
+}
+
+#[wasm_bindgen_test]
+fn component_defaults() {
+    let d = doc();
+    let div = jsx!(d => {
+        <div>
+            <Badge text="a" />
+            <Badge count={ 3 } text="b" kind="warning" title={ Some("B") } />
+        </div>
+    });
+
+    assert_eq!(
+        div.inner_html(),
+        "<span class=\"info\">a (0)</span><span class=\"warning\" title=\"B\">b (3)</span>",
+    );
+}
+
+/// A card showing the name of a user.
+#[derive(Clone, Debug, domsl::Component)]
+pub struct UserCard {
+    /// The name of the user.
+    pub name: String,
+    #[domsl(default)]
+    admin: bool,
+}
+
+impl UserCard {
+    fn render<B: Backend>(
+        self,
+        backend: &B,
+        children: Vec<B::Node>,
+    ) -> B::Element<web_sys::HtmlDivElement> {
+        jsx!(backend => { <div class:admin={ self.admin }>{ self.name }{ children }</div> })
+    }
+}
+
+#[derive(domsl::Component)]
+#[domsl(render = render_link)]
+struct Link<'a> {
+    href: &'a str,
+}
+
+fn render_link<B: Backend>(
+    link: Link<'_>,
+    backend: &B,
+    children: Vec<B::Node>,
+) -> B::Element<web_sys::HtmlAnchorElement> {
+    jsx!(backend => { <a href={ link.href }>{ children }</a> })
+}
+
+#[wasm_bindgen_test]
+fn derived_component() {
+    let d = doc();
+    let div = jsx!(d => {
+        <div>
+            <UserCard name={ "Anna".into() } admin={ true }>"!"</UserCard>
+            <UserCard name={ "Bob".into() } />
+            <Link href="/home">"Home"</Link>
+        </div>
+    });
+
+    assert_eq!(
+        div.inner_html(),
+        "<div class=\"admin\">Anna!</div><div>Bob</div><a href=\"/home\">Home</a>",
+    );
+}
+
+#[wasm_bindgen_test]
+fn shadow_root() {
+    let d = doc();
+    let div: web_sys::HtmlDivElement = jsx!(d => {
+        <div>
+            <#shadow><b><slot /></b></#shadow>
+            "light"
+        </div>
+    });
+
+    assert_eq!(div.inner_html(), "light");
+    assert_eq!(div.shadow_root().unwrap().inner_html(), "<b><slot></slot></b>");
+}
+
+#[wasm_bindgen_test]
+fn properties() {
+    let d = doc();
+    let input: web_sys::HtmlInputElement = jsx!(d => {
+        <input type="checkbox" value="default" prop:value={ "live" } prop:checked={ true } />
+    });
+
+    assert_eq!(input.value(), "live");
+    assert_eq!(input.get_attribute("value").as_deref(), Some("default"));
+    assert!(input.checked());
+    assert!(!input.has_attribute("checked"));
+}
+
+#[wasm_bindgen_test]
+fn element_refs() {
+    let d = doc();
+    let input = domsl::ElementRef::new();
+    let _form: web_sys::HtmlFormElement = jsx!(d => {
+        <form><input ref={input} prop:value={ "hi" } /></form>
+    });
+
+    let input: web_sys::HtmlInputElement = input.get().unwrap();
+    assert_eq!(input.value(), "hi");
+}
@@ -0,0 +1,914 @@
+#![feature(proc_macro_hygiene)]
+
+//! The tests from `jsx.rs`, but using the mock DOM so that they can run
+//! without a browser.
+
+use domsl::{
+    jsx,
+    mock::{Document, Node},
+    ssr::Html,
+    Backend, Component, ElementRef, Props,
+};
+
+
+// ===== Helper functions and macros =========================================
+
+fn doc() -> Document {
+    Document::new()
+}
+
+macro_rules! assert_only_has_text {
+    ($n:ident, $t:literal) => {
+        assert_eq!($n.text_content(), $t);
+        assert_eq!($n.inner_html(), $t);
+        assert_eq!($n.children().len(), 0);
+    };
+}
+
+macro_rules! assert_tag {
+    ($n:ident, $tag:literal) => {
+        assert_eq!($n.tag_name().as_deref(), Some($tag), "node: {:?}", $n);
+    };
+}
+
+macro_rules! assert_into_children {
+    ($n:ident, $count:literal) => {{
+        let children = $n.children();
+        if children.len() != $count {
+            panic!(
+                "expected '{}' to have {} children, but it has {} children",
+                stringify!($n),
+                $count,
+                children.len(),
+            );
+        }
+
+        ::std::convert::TryInto::<[_; $count]>::try_into(children).unwrap()
+    }};
+}
+
+
+
+// ===== Actual tests ========================================================
+
+#[test]
+fn simple_div() {
+    let d = doc();
+    let n = jsx!(d => { <div></div> });
+    assert_tag!(n, "div");
+    assert_only_has_text!(n, "");
+}
+
+#[test]
+fn span_child() {
+    let d = doc();
+    let n = jsx!(d => {
+        <div><span>"Hello"</span></div>
+    });
+
+    assert!(!n.text_content().is_empty());
+    assert!(!n.inner_html().is_empty());
+    let [span] = assert_into_children!(n, 1);
+
+    assert_tag!(span, "span");
+    assert_only_has_text!(span, "Hello");
+}
+
+#[test]
+fn all_into_node() {
+    let d = doc();
+
+    // We test all implementations that can convert a value into a node.
+    let p_node = jsx!(d => {
+        <p>"Hello p"</p>
+    });
+    let s1 = "s1";
+    let s2 = String::from("s2");
+    let node_vec = vec![
+        jsx!(d => { <span>"span1"</span> }),
+        jsx!(d => { <span>"span2"</span> }),
+    ];
+
+    let out = jsx!(d => {
+        <div class="baz">
+            { p_node }
+            <div>{ s1 }</div>
+            <div>{ s2 }</div>
+            <div>{ 27 }</div>
+            <div>{ node_vec }</div>
+            <div>{ vec!["hi", "yo"] }</div>
+            <div>{ &[1, 2, 3] }</div>
+        </div>
+    });
+
+    let [c0, c1, c2, c3, c4, c5, c6] = assert_into_children!(out, 7);
+
+    assert_tag!(c0, "p");
+    assert_only_has_text!(c0, "Hello p");
+    assert_tag!(c1, "div");
+    assert_only_has_text!(c1, "s1");
+    assert_tag!(c2, "div");
+    assert_only_has_text!(c2, "s2");
+    assert_tag!(c3, "div");
+    assert_only_has_text!(c3, "27");
+
+    assert_tag!(c5, "div");
+    assert_only_has_text!(c5, "hiyo");
+    assert_tag!(c6, "div");
+    assert_only_has_text!(c6, "123");
+
+    let [cc0, cc1] = assert_into_children!(c4, 2);
+    assert_tag!(cc0, "span");
+    assert_only_has_text!(cc0, "span1");
+    assert_tag!(cc1, "span");
+    assert_only_has_text!(cc1, "span2");
+}
+
+#[test]
+fn attributes_and_html() {
+    let d = doc();
+    let n = jsx!(d => {
+        <p class="a" id={ 3 }>"x < y"<br />{ "z" }</p>
+    });
+
+    assert_eq!(n.get_attribute("class").as_deref(), Some("a"));
+    assert_eq!(n.get_attribute("id").as_deref(), Some("3"));
+    assert_eq!(n.get_attribute("title"), None);
+    assert_eq!(n.child_nodes().len(), 3);
+    assert_eq!(n.text_content(), "x < yz");
+    assert_eq!(n.outer_html(), r#"<p class="a" id="3">x &lt; y<br>z</p>"#);
+}
+
+#[test]
+fn append_moves_node() {
+    let d = doc();
+    let span = jsx!(d => { <span>"hi"</span> });
+    let a = jsx!(d => { <div>{ &span }</div> });
+    assert_eq!(span.parent_node(), Some(a.clone()));
+
+    let b = jsx!(d => { <div>{ &span }</div> });
+    assert_eq!(span.parent_node(), Some(b.clone()));
+    assert_eq!(a.inner_html(), "");
+    assert_eq!(b.inner_html(), "<span>hi</span>");
+}
+
+#[test]
+fn names_with_hyphens() {
+    let d = doc();
+    let n = jsx!(d => {
+        <div data-0="a" data-2x="b" data-foo-1-bar="c" aria-label="d">
+            <x-item-2 data-id="e" />
+        </div>
+    });
+
+    assert_eq!(
+        n.outer_html(),
+        "<div data-0=\"a\" data-2x=\"b\" data-foo-1-bar=\"c\" aria-label=\"d\">\
+            <x-item-2 data-id=\"e\"></x-item-2></div>",
+    );
+}
+
+#[test]
+fn fragments_and_self_closing_tags() {
+    let d = doc();
+    let n = jsx!(d => {
+        <>
+            <br />
+            <>"a"<i />"b"</>
+            <p></p>
+        </>
+    });
+
+    assert!(n.is_fragment());
+    assert_eq!(n.inner_html(), "<br>a<i></i>b<p></p>");
+}
+
+#[test]
+fn content_models() {
+    let d = doc();
+
+    // Transparent elements allow what their parent allows.
+    let n = jsx!(d => {
+        <p>
+            <a href="/"><b>"link"</b></a>
+            <noscript><i>"no JS"</i></noscript>
+            <script>"if (1 < 2) {}"</script>
+        </p>
+    });
+    assert_eq!(
+        n.inner_html(),
+        "<a href=\"/\"><b>link</b></a><noscript><i>no JS</i></noscript>\
+            <script>if (1 < 2) {}</script>",
+    );
+
+    // The content of `<template>` is not restricted.
+    let n = jsx!(d => { <template><li>"a"</li><tr><td /></tr></template> });
+    assert_eq!(n.inner_html(), "<li>a</li><tr><td></td></tr>");
+}
+
+#[test]
+fn generic_component() {
+    #[derive(Props)]
+    struct Item {
+        label: &'static str,
+    }
+
+    impl<B: Backend> Component<B> for Item {
+        type Node = B::Element<web_sys::HtmlLiElement>;
+        fn render(self, backend: &B, children: Vec<B::Node>) -> Self::Node {
+            jsx!(backend => { <li>{ self.label }": "{ children }</li> })
+        }
+    }
+
+    let d = doc();
+    let n = jsx!(d => {
+        <ul>
+            <Item label="a">"1"</Item>
+            <Item label="b"><b>"2"</b></Item>
+        </ul>
+    });
+    assert_eq!(n.inner_html(), "<li>a: 1</li><li>b: <b>2</b></li>");
+}
+
+#[domsl::component]
+fn greeting<B: Backend>(
+    #[domsl(backend)] backend: &B,
+    name: &str,
+    #[domsl(default)] excited: bool,
+    #[domsl(children)] children: Vec<B::Node>,
+) -> B::Element<web_sys::HtmlParagraphElement> {
+    let mark = if excited { "!" } else { "." };
+    jsx!(backend => { <p>"Hello "{ name }{ mark }{ children }</p> })
+}
+
+#[test]
+fn function_component() {
+    let d = doc();
+    let n = jsx!(d => {
+        <div>
+            <Greeting name="Anna" excited={ true }><b>"?"</b></Greeting>
+            <Greeting name="Bob" />
+        </div>
+    });
+    assert_eq!(n.inner_html(), "<p>Hello Anna!<b>?</b></p><p>Hello Bob.</p>");
+}
+
+// `U` is only used in the where clause.
+#[domsl::component]
+fn deref_text<B: Backend, T, U>(
+    #[domsl(backend)] backend: &B,
+    value: T,
+) -> B::Element<web_sys::HtmlSpanElement>
+where
+    T: std::ops::Deref<Target = U>,
+    U: std::fmt::Display + ?Sized,
+{
+    jsx!(backend => { <span>{ &*value }</span> })
+}
+
+#[test]
+fn component_with_unused_param() {
+    let d = doc();
+    let n = jsx!(d => {
+        <div>
+            <DerefText value={ Box::new(3) } />
+            <DerefText value={ "str".to_string() } />
+        </div>
+    });
+    assert_eq!(n.inner_html(), "<span>3</span><span>str</span>");
+}
+
+#[test]
+fn derived_component() {
+    #[derive(Component)]
+    struct UserCard {
+        name: &'static str,
+        #[domsl(default)]
+        admin: bool,
+    }
+
+    impl UserCard {
+        fn render<B: Backend>(
+            self,
+            backend: &B,
+            children: Vec<B::Node>,
+        ) -> B::Element<web_sys::HtmlDivElement> {
+            jsx!(backend => { <div class:admin={ self.admin }>{ self.name }{ children }</div> })
+        }
+    }
+
+    #[derive(Component)]
+    #[domsl(render = render_link)]
+    struct Link<'a> {
+        href: &'a str,
+    }
+
+    fn render_link<B: Backend>(link: Link<'_>, backend: &B, children: Vec<B::Node>) -> B::Node {
+        jsx!(backend => { <a href={ link.href }>{ children }</a> }).into()
+    }
+
+    let d = doc();
+    let n = jsx!(d => {
+        <div>
+            <UserCard name="Anna" admin={ true }>"!"</UserCard>
+            <UserCard name="Bob" />
+            <Link href="/home">"Home"</Link>
+        </div>
+    });
+    assert_eq!(
+        n.inner_html(),
+        "<div class=\"admin\">Anna!</div><div>Bob</div><a href=\"/home\">Home</a>",
+    );
+}
+
+#[test]
+fn default_props() {
+    #[derive(Props)]
+    struct Button {
+        label: &'static str,
+        #[domsl(default = "button")]
+        kind: &'static str,
+        #[domsl(default)]
+        disabled: bool,
+        title: Option<String>,
+    }
+
+    impl<B: Backend> Component<B> for Button {
+        type Node = B::Element<web_sys::HtmlButtonElement>;
+        fn render(self, backend: &B, _: Vec<B::Node>) -> Self::Node {
+            jsx!(backend => {
+                <button type={ self.kind } disabled={ self.disabled } title={ self.title }>
+                    { self.label }
+                </button>
+            })
+        }
+    }
+
+    let d = doc();
+    let n = jsx!(d => {
+        <div>
+            <Button label="a" />
+            <Button disabled={ true } label="b" kind="submit" title={ Some("B".to_string()) } />
+        </div>
+    });
+    assert_eq!(
+        n.inner_html(),
+        "<button type=\"button\">a</button>\
+            <button type=\"submit\" disabled=\"\" title=\"B\">b</button>",
+    );
+}
+
+#[test]
+fn custom_element_attributes() {
+    use domsl::custom_element::{parse_attribute, FromAttribute};
+
+    fn parse<T: FromAttribute>(value: Option<&str>, default: T) -> (T, Option<String>) {
+        let mut warning = None;
+        let value = parse_attribute(value, || default, |msg| warning = Some(msg.to_string()));
+        (value, warning)
+    }
+
+    assert_eq!(parse(Some(" 2 "), 3u32), (2, None));
+    assert_eq!(parse(None, 3u32), (3, None));
+    let (count, warning) = parse(Some("many"), 3u32);
+    assert_eq!(count, 3);
+    assert!(warning.unwrap().starts_with("'many' is not a valid `u32`"));
+
+    assert_eq!(parse(Some(""), false), (true, None));
+    assert_eq!(parse(None, bool::missing()), (false, None));
+    let (number, warning) = parse(Some("x"), Option::<i8>::missing());
+    assert_eq!(number, None);
+    assert_eq!(warning.unwrap(), "'x' is not a valid `i8`: invalid digit found in string");
+    assert_eq!(parse(None, String::missing()), (String::new(), None));
+}
+
+#[test]
+fn conditional() {
+    let d = doc();
+    let list = |logged_in: bool, name: Option<&str>| jsx!(d => {
+        <ul>
+            {if logged_in {
+                <li>"logged in"</li>
+            } else {
+                <li>"logged out"</li>
+                <li>"please log in"</li>
+            }}
+            {if let Some(name) = name {
+                <li>"Hi "{ name }</li>
+            }}
+            {if name == Some("admin") {
+                <li>"admin"</li>
+            } else if logged_in {
+                <li>"no admin"</li>
+            }}
+        </ul>
+    });
+
+    assert_eq!(
+        list(true, Some("admin")).inner_html(),
+        "<li>logged in</li><li>Hi admin</li><li>admin</li>",
+    );
+    assert_eq!(list(true, None).inner_html(), "<li>logged in</li><li>no admin</li>");
+    assert_eq!(
+        list(false, None).inner_html(),
+        "<li>logged out</li><li>please log in</li>",
+    );
+
+    let root = jsx!(d => { {if true { <b>"yes"</b> }} });
+    assert_eq!(root.outer_html(), "<b>yes</b>");
+}
+
+#[test]
+fn rust_if_as_content() {
+    let d = doc();
+    let (a, b) = ("a", "b");
+    let p = |x: bool| jsx!(d => { <p>{ if x { a } else { b } }</p> });
+    assert_eq!(p(true).outer_html(), "<p>a</p>");
+    assert_eq!(p(false).outer_html(), "<p>b</p>");
+
+    let n = jsx!(d => { <p>{ if a.len() < 2 { a.len() } else { 0 } }</p> });
+    assert_eq!(n.outer_html(), "<p>1</p>");
+}
+
+#[test]
+fn rust_match_as_content() {
+    enum Size {
+        Small,
+        Large,
+    }
+
+    let d = doc();
+    let (small, large) = ("small", "large");
+    let p = |size: Size| jsx!(d => {
+        <p>{ match size { Size::Small => small, Size::Large => large } }</p>
+    });
+    assert_eq!(p(Size::Small).outer_html(), "<p>small</p>");
+    assert_eq!(p(Size::Large).outer_html(), "<p>large</p>");
+
+    let n = jsx!(d => { <p>{ match 3 { x if x < 5 => x * 2, _ => 0 } }</p> });
+    assert_eq!(n.outer_html(), "<p>6</p>");
+}
+
+#[test]
+fn loops() {
+    struct User {
+        name: &'static str,
+        admin: bool,
+    }
+
+    let users = [
+        User { name: "Anna", admin: true },
+        User { name: "Bob", admin: false },
+    ];
+
+    let d = doc();
+    let n = jsx!(d => {
+        <ul>
+            <li>"first"</li>
+            {for (i, user) in users.iter().enumerate() {
+                <li>{ i }": "{ user.name }</li>
+                {if user.admin {
+                    <li>"(admin)"</li>
+                }}
+            }}
+        </ul>
+    });
+    assert_eq!(
+        n.inner_html(),
+        "<li>first</li><li>0: Anna</li><li>(admin)</li><li>1: Bob</li>",
+    );
+
+    let root = jsx!(d => { {for i in 1..4 { <b>{ i }</b> }} });
+    assert!(root.is_fragment());
+    assert_eq!(root.inner_html(), "<b>1</b><b>2</b><b>3</b>");
+}
+
+#[test]
+fn matches() {
+    enum State {
+        Loading,
+        Ready(Vec<&'static str>),
+        Failed(String),
+    }
+
+    let d = doc();
+    let view = |state: &State| jsx!(d => {
+        <div>
+            {match state {
+                State::Loading => <p class="spinner">"Loading..."</p>,
+                State::Ready(items) if items.is_empty() => "Nothing here",
+                State::Ready(items) => <ul>{for item in items { <li>{ item }</li> }}</ul>,
+                State::Failed(e) => <>"Error: "<b>{ e }</b></>
+            }}
+        </div>
+    });
+
+    assert_eq!(
+        view(&State::Loading).inner_html(),
+        r#"<p class="spinner">Loading...</p>"#,
+    );
+    assert_eq!(view(&State::Ready(vec![])).inner_html(), "Nothing here");
+    assert_eq!(
+        view(&State::Ready(vec!["a", "b"])).inner_html(),
+        "<ul><li>a</li><li>b</li></ul>",
+    );
+    assert_eq!(
+        view(&State::Failed("oops".into())).inner_html(),
+        "Error: <b>oops</b>",
+    );
+}
+
+#[test]
+fn attribute_values() {
+    let d = doc();
+    let input = |disabled: bool, title: Option<&str>| jsx!(d => {
+        <input disabled={ disabled } title={ title } size={ 10u8 } value={ 1.5 } />
+    });
+
+    let n = input(true, Some("t"));
+    assert_eq!(n.get_attribute("disabled").as_deref(), Some(""));
+    assert_eq!(n.get_attribute("title").as_deref(), Some("t"));
+    assert_eq!(n.get_attribute("size").as_deref(), Some("10"));
+    assert_eq!(n.get_attribute("value").as_deref(), Some("1.5"));
+
+    let n = input(false, None);
+    assert_eq!(n.get_attribute("disabled"), None);
+    assert_eq!(n.get_attribute("title"), None);
+    assert_eq!(n.outer_html(), r#"<input size="10" value="1.5">"#);
+
+    // Only boolean attributes are removed by `false`.
+    let flags = |on: bool| jsx!(d => {
+        <div autofocus={ on } draggable={ on } spellcheck={ on }
+            {..vec![("hidden", on), ("translate", on)]} />
+    });
+    assert_eq!(
+        flags(true).outer_html(),
+        "<div autofocus=\"\" draggable=\"true\" spellcheck=\"true\" hidden=\"\" \
+            translate=\"true\"></div>",
+    );
+    assert_eq!(
+        flags(false).outer_html(),
+        r#"<div draggable="false" spellcheck="false" translate="false"></div>"#,
+    );
+}
+
+#[test]
+fn data_and_aria_attributes() {
+    let d = doc();
+    let id = 7;
+    let n = jsx!(d => {
+        <button data-id={ id } data-test-name="save" aria-label="Save" aria-pressed={ false }>
+            "S"
+        </button>
+    });
+    assert_eq!(n.get_attribute("data-id").as_deref(), Some("7"));
+    assert_eq!(n.get_attribute("data-test-name").as_deref(), Some("save"));
+    assert_eq!(n.get_attribute("aria-label").as_deref(), Some("Save"));
+    assert_eq!(n.get_attribute("aria-pressed").as_deref(), Some("false"));
+
+    let n = jsx!(d => { <div aria-expanded={ true } aria-hidden={ false } data-open={ true } /> });
+    assert_eq!(n.get_attribute("aria-expanded").as_deref(), Some("true"));
+    assert_eq!(n.get_attribute("aria-hidden").as_deref(), Some("false"));
+    assert_eq!(n.get_attribute("data-open").as_deref(), Some("true"));
+}
+
+#[test]
+fn svg() {
+    const SVG: &str = "http://www.w3.org/2000/svg";
+
+    let d = doc();
+    let points = vec![(1, 2), (3, 4)];
+    let n = jsx!(d => {
+        <div>
+            <svg viewBox="0 0 10 10" width="10">
+                <defs>
+                    <linearGradient id="g"><stop offset="0" stop-color="red" /></linearGradient>
+                </defs>
+                <g fill="url(#g)">
+                    {for (x, y) in &points {
+                        <circle cx={ x } cy={ y } r="1" />
+                    }}
+                </g>
+                <text x="0" y="10">"a < b"<tspan>"!"</tspan></text>
+                <a href="/"><title>"link"</title></a>
+            </svg>
+        </div>
+    });
+
+    assert_eq!(n.namespace_uri().as_deref(), Some("http://www.w3.org/1999/xhtml"));
+    let [svg] = assert_into_children!(n, 1);
+    assert_eq!(svg.namespace_uri().as_deref(), Some(SVG));
+    let [defs, g, text, a] = assert_into_children!(svg, 4);
+    assert_eq!(defs.children()[0].tag_name().as_deref(), Some("linearGradient"));
+    assert_eq!(g.children()[1].namespace_uri().as_deref(), Some(SVG));
+    assert_eq!(
+        g.inner_html(),
+        r#"<circle cx="1" cy="2" r="1"></circle><circle cx="3" cy="4" r="1"></circle>"#,
+    );
+    assert_eq!(text.inner_html(), "a &lt; b<tspan>!</tspan>");
+    assert_eq!(a.namespace_uri().as_deref(), Some(SVG));
+
+    // SVG elements can also be used without `<svg>` around them (e.g. in
+    // components), as long as the name is not also an HTML tag.
+    let path = jsx!(d => { <path d="M 0 0 L 1 1" /> });
+    assert_eq!(path.namespace_uri().as_deref(), Some(SVG));
+}
+
+#[test]
+fn mathml() {
+    const MATHML: &str = "http://www.w3.org/1998/Math/MathML";
+
+    let d = doc();
+    let n = jsx!(d => {
+        <p>
+            "Formula: "
+            <math display="block">
+                <mfrac>
+                    <mi>"a"</mi>
+                    <mrow><mn>"2"</mn><mo>"+"</mo><msup><mi>"b"</mi><mn>"2"</mn></msup></mrow>
+                </mfrac>
+            </math>
+        </p>
+    });
+
+    let [math] = assert_into_children!(n, 1);
+    assert_eq!(math.namespace_uri().as_deref(), Some(MATHML));
+    assert_eq!(math.get_attribute("display").as_deref(), Some("block"));
+    let [mfrac] = assert_into_children!(math, 1);
+    assert_eq!(mfrac.namespace_uri().as_deref(), Some(MATHML));
+    assert_eq!(mfrac.text_content(), "a2+b2");
+}
+
+#[test]
+fn custom_elements() {
+    let d = doc();
+    let label = "Save";
+    let n = jsx!(d => {
+        <div>
+            <my-widget some-option="1" label={ label }>
+                <x-icon kind="disk" />
+                "text"
+            </my-widget>
+        </div>
+    });
+
+    let [widget] = assert_into_children!(n, 1);
+    assert_eq!(widget.tag_name().as_deref(), Some("my-widget"));
+    assert_eq!(widget.get_attribute("some-option").as_deref(), Some("1"));
+    assert_eq!(widget.get_attribute("label").as_deref(), Some("Save"));
+    assert_eq!(widget.inner_html(), r#"<x-icon kind="disk"></x-icon>text"#);
+
+    // Custom elements as root are typed as `HtmlElement`.
+    fn expect_html_element<B: Backend>(_: &B::Element<web_sys::HtmlElement>) {}
+    let root = jsx!(d => { <my-widget /> });
+    expect_html_element::<Document>(&root);
+    assert_eq!(root.tag_name().as_deref(), Some("my-widget"));
+}
+
+#[test]
+fn declared_custom_elements() {
+    // `x-rating` is declared in `custom-elements.toml`.
+    let d = doc();
+    let n = jsx!(d => { <x-rating stars={ 4 } title="Rating"><span>"★"</span></x-rating> });
+    assert_eq!(n.get_attribute("stars").as_deref(), Some("4"));
+    assert_eq!(n.get_attribute("title").as_deref(), Some("Rating"));
+    assert_eq!(n.inner_html(), "<span>★</span>");
+}
+
+#[test]
+fn shadow_root() {
+    let d = doc();
+    let n = jsx!(d => {
+        <div class="card">
+            <#shadow>
+                <style>"p { color: red; }"</style>
+                <p><slot name="title" />"!"</p>
+                <slot />
+            </#shadow>
+            <span slot="title">"Title"</span>
+            "content"
+        </div>
+    });
+
+    // The light DOM doesn't contain the shadow root.
+    assert_eq!(n.inner_html(), r#"<span slot="title">Title</span>content"#);
+    let shadow = n.shadow_root().unwrap();
+    assert!(shadow.is_fragment());
+    assert_eq!(
+        shadow.inner_html(),
+        r#"<style>p { color: red; }</style><p><slot name="title"></slot>!</p><slot></slot>"#,
+    );
+
+    // Closed shadow roots are not accessible.
+    let n = jsx!(d => { <my-widget><#shadow mode="closed">"secret"</#shadow></my-widget> });
+    assert_eq!(n.shadow_root(), None);
+
+    // But both are rendered by `to_html`.
+    assert_eq!(
+        n.to_html().as_str(),
+        r#"<my-widget><template shadowrootmode="closed">secret</template></my-widget>"#,
+    );
+}
+
+#[test]
+fn raw_html() {
+    let d = doc();
+    let raw = Node::from(Html::from_raw("<b>bold</b>".into()));
+    let n = jsx!(d => { <p>"<i>"{ raw }</p> });
+
+    assert_eq!(n.inner_html(), "&lt;i&gt;<b>bold</b>");
+    assert_eq!(n.text_content(), "<i><b>bold</b>");
+    assert!(n.children().is_empty());
+}
+
+#[test]
+fn properties() {
+    let d = doc();
+    let text = String::from("typed");
+    let n = jsx!(d => {
+        <form>
+            <input value="default" prop:value={ &text } prop:checked={ true } />
+            <select prop:selectedIndex={ 2 }><option>"a"</option></select>
+        </form>
+    });
+
+    let [input, select] = assert_into_children!(n, 2);
+    assert_eq!(input.get_attribute("value").as_deref(), Some("default"));
+    assert_eq!(input.property("value").as_deref(), Some(r#""typed""#));
+    assert_eq!(input.property("checked").as_deref(), Some("true"));
+    assert_eq!(select.property("selectedIndex").as_deref(), Some("2"));
+
+    // Properties are not attributes.
+    assert_eq!(input.attributes().len(), 1);
+    assert_eq!(n.inner_html(), r#"<input value="default"><select><option>a</option></select>"#);
+}
+
+#[test]
+fn checkbox_properties() {
+    let d = doc();
+    let checked = false;
+    let n = jsx!(d => {
+        <div>
+            <input type="checkbox" value="default" prop:value={ "live" } prop:checked={ true } />
+            <input type="checkbox" prop:checked={ checked } />
+        </div>
+    });
+
+    let [a, b] = assert_into_children!(n, 2);
+    assert_eq!(a.property("value").as_deref(), Some(r#""live""#));
+    assert_eq!(a.get_attribute("value").as_deref(), Some("default"));
+    assert_eq!(a.property("checked").as_deref(), Some("true"));
+    assert_eq!(a.get_attribute("checked"), None);
+    assert_eq!(b.property("checked").as_deref(), Some("false"));
+    assert_eq!(b.property("value"), None);
+}
+
+#[test]
+fn element_refs() {
+    let d = doc();
+    let input = ElementRef::new();
+    let item = ElementRef::new();
+    let form = jsx!(d => {
+        <form>
+            <label>"Name: "<input ref={input} name="name" /></label>
+            <ul>
+                {for i in 0..3 {
+                    <li ref={item}>{ i }</li>
+                }}
+            </ul>
+        </form>
+    });
+
+    let input: Node = input.get().unwrap();
+    assert_eq!(input.get_attribute("name").as_deref(), Some("name"));
+    assert_eq!(input.parent_node().unwrap().tag_name().as_deref(), Some("label"));
+
+    // In loops, the ref points to the last element.
+    assert_eq!(item.get().unwrap().text_content(), "2");
+    assert_eq!(form.children()[1].children()[2], item.get().unwrap());
+}
+
+#[test]
+fn input_events_are_plain_events() {
+    let d = doc();
+    let n = jsx!(d => {
+        <form>
+            <select on:input={|_: web_sys::Event| ()}><option>"a"</option></select>
+            <input type="checkbox" on:input={|_: web_sys::Event| ()} />
+        </form>
+    });
+    assert_eq!(n.children().len(), 2);
+}
+
+#[test]
+fn nested_element_ref() {
+    let d = doc();
+    let r = ElementRef::new();
+    let unused = ElementRef::<Node>::new();
+    let form = jsx!(d => {
+        <form>
+            <input ref={r} prop:value={ "hi" } />
+            {if false { <input ref={unused} /> }}
+        </form>
+    });
+
+    let input: Node = r.get().unwrap();
+    assert_tag!(input, "input");
+    assert_eq!(input.property("value").as_deref(), Some(r#""hi""#));
+    assert_eq!(input.parent_node(), Some(form));
+    assert!(unused.get().is_none());
+}
+
+#[test]
+fn spread_attributes() {
+    let d = doc();
+    let attrs = vec![("id", Some("main")), ("class", Some("spread")), ("title", None)];
+    let mut more = std::collections::BTreeMap::new();
+    more.insert("data-x".to_string(), 3);
+    more.insert("hidden".to_string(), 4);
+
+    let n = jsx!(d => { <div class="fixed" {..attrs} hidden={ false } {..&more} /> });
+    assert_eq!(
+        n.attributes(),
+        vec![
+            ("class".to_string(), "fixed".to_string()),
+            ("id".to_string(), "main".to_string()),
+            ("data-x".to_string(), "3".to_string()),
+        ],
+    );
+}
+
+#[test]
+fn spread_attributes_invalid_name() {
+    let d = doc();
+    let attrs = vec![
+        ("onclick=\"alert(1)\" x", ""),
+        ("id", "ok"),
+        ("a b", ""),
+        ("", ""),
+        ("1abc", ""),
+        ("a\u{0}", ""),
+        ("data-ok.x", "1"),
+    ];
+    let n = jsx!(d => { <div {..attrs} /> });
+    assert_eq!(n.outer_html(), r#"<div id="ok" data-ok.x="1"></div>"#);
+}
+
+#[test]
+fn spread_attributes_warning_handler() {
+    use std::sync::Mutex;
+
+    static WARNINGS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+    domsl::attribute::set_warning_handler(|msg| WARNINGS.lock().unwrap().push(msg.into()));
+
+    let d = doc();
+    let n = jsx!(d => { <div {..vec![("9lives", "")]} /> });
+    assert_eq!(n.outer_html(), "<div></div>");
+    assert!(WARNINGS.lock().unwrap().iter().any(|w| w.contains("\"9lives\"")));
+}
+
+#[test]
+fn class_directives() {
+    let d = doc();
+    let active = true;
+    let extra = vec!["big", "red"];
+    let n = jsx!(d => {
+        <div>
+            <button class="btn" class={ &extra } class:active={ active } class:off={ !active } />
+            <span class:a={ false } />
+            <span class={ extra.iter().map(|c| format!("x-{}", c)) } />
+            <span class={ None::<&str> } class:is-b={ true } {..vec![("class", "spread")]} />
+        </div>
+    });
+
+    let [button, a, b, c] = assert_into_children!(n, 4);
+    assert_eq!(button.get_attribute("class").as_deref(), Some("btn big red active"));
+    assert_eq!(a.get_attribute("class"), None);
+    assert_eq!(b.get_attribute("class").as_deref(), Some("x-big x-red"));
+    assert_eq!(c.get_attribute("class").as_deref(), Some("is-b"));
+}
+
+#[test]
+fn style_directives() {
+    let d = doc();
+    let color = "red";
+    let width: Option<u32> = None;
+    let n = jsx!(d => {
+        <div>
+            <p style="margin: 0;" style:color={ color } style:font-size="12px"
+                style:width={ width } />
+            <span style:width={ width } />
+            <span style={ format!("top: {}px", 3) } style:z-index={ 2 } />
+            <span style={ None::<&str> } {..vec![("style", "left: 0")]} />
+        </div>
+    });
+
+    let [p, a, b, c] = assert_into_children!(n, 4);
+    assert_eq!(
+        p.get_attribute("style").as_deref(),
+        Some("margin: 0; color: red; font-size: 12px"),
+    );
+    assert_eq!(a.get_attribute("style"), None);
+    assert_eq!(b.get_attribute("style").as_deref(), Some("top: 3px; z-index: 2"));
+    assert_eq!(c.get_attribute("style"), None);
+}
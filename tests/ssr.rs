@@ -0,0 +1,306 @@
+#![feature(proc_macro_hygiene)]
+
+use domsl::{
+    html, jsx,
+    ssr::{Html, Renderer},
+    Backend, Component, Props,
+};
+
+
+#[derive(Props)]
+struct Greeting {
+    name: &'static str,
+}
+
+impl<B: Backend> Component<B> for Greeting {
+    type Node = B::Element<web_sys::HtmlParagraphElement>;
+    fn render(self, backend: &B, children: Vec<B::Node>) -> Self::Node {
+        jsx!(backend => {
+            <p>"Hello "{ self.name }{ children }</p>
+        })
+    }
+}
+
+
+#[test]
+fn simple() {
+    let h = html! { <div class="foo"><span>"Hello"</span><br /></div> };
+    assert_eq!(h.as_str(), r#"<div class="foo"><span>Hello</span><br></div>"#);
+}
+
+#[test]
+fn escaping() {
+    let name = "<Peter> & \"Paul\"";
+    let h = html! {
+        <p title={ name }>"a < b"{ name }</p>
+    };
+    assert_eq!(
+        h.as_str(),
+        "<p title=\"&lt;Peter&gt; &amp; &quot;Paul&quot;\">\
+            a &lt; b&lt;Peter&gt; &amp; \"Paul\"</p>",
+    );
+
+    let h = html! { <style>"p > span { color: red; }"</style> };
+    assert_eq!(h.as_str(), "<style>p > span { color: red; }</style>");
+}
+
+#[test]
+fn interpolation() {
+    let inner = html! { <b>"bold"</b> };
+    let inners = vec![html! { <i>"1"</i> }, html! { <i>"2"</i> }];
+    let h = html! {
+        <div>
+            { &inner }
+            <p>{ String::from("s") }{ 27 }</p>
+            <p>{ inners }</p>
+            <p>{ vec!["<", ">"] }{ &[1, 2] }</p>
+        </div>
+    };
+    assert_eq!(
+        h.as_str(),
+        "<div><b>bold</b><p>s27</p><p><i>1</i><i>2</i></p><p>&lt;&gt;12</p></div>",
+    );
+}
+
+#[test]
+fn fragment() {
+    let h: Html = html! { <><li>"a"</li><li>"b"</li></> };
+    assert_eq!(h.to_string(), "<li>a</li><li>b</li>");
+}
+
+#[test]
+fn renderer_backend() {
+    let node = jsx!(Renderer => {
+        <div id="a&b">
+            <span on:click={|_| {}}>"<x>"</span>
+            <>{ vec!["a", "b"] }{ 3 }</>
+            <script>"1 < 2"</script>
+            <input />
+        </div>
+    });
+    assert_eq!(
+        node.to_html().as_str(),
+        r#"<div id="a&amp;b"><span>&lt;x&gt;</span>ab3<script>1 < 2</script><input></div>"#,
+    );
+}
+
+#[test]
+fn raw_text() {
+    let code = "if (a </b/) {}</script><script>evil()";
+    let expected = "<script>let s = 1 < 2;\
+        if (a <\\/b/) {}<\\/script><script>evil()<\\/</script>";
+
+    let h = html! { <script>"let s = 1 < 2;"{ code }"<"{ '/' }</script> };
+    assert_eq!(h.as_str(), expected);
+
+    let node = jsx!(Renderer => {
+        <script>"let s = 1 < 2;"{ code }"<"{ '/' }</script>
+    });
+    assert_eq!(node.to_html().as_str(), expected);
+}
+
+#[test]
+fn component() {
+    let h = html! {
+        <div>
+            <Greeting name="Peter"><b>"!"</b></Greeting>
+        </div>
+    };
+    assert_eq!(h.as_str(), "<div><p>Hello Peter<b>!</b></p></div>");
+
+    let node = jsx!(Renderer => { <Greeting name="Paul" /> });
+    assert_eq!(node.to_html().as_str(), "<p>Hello Paul</p>");
+}
+
+#[domsl::component]
+fn badge<B: Backend>(
+    #[domsl(backend)] backend: &B,
+    label: &str,
+    count: u32,
+) -> B::Element<web_sys::HtmlSpanElement> {
+    jsx!(backend => { <span class="badge">{ label }": "{ count }</span> })
+}
+
+#[test]
+fn function_component() {
+    let h = html! { <p><Badge label="Inbox" count={ 3 } /></p> };
+    assert_eq!(h.as_str(), r#"<p><span class="badge">Inbox: 3</span></p>"#);
+}
+
+#[test]
+fn conditional() {
+    let greet = |name: Option<&str>| html! {
+        <p>
+            {if let Some(name) = name {
+                "Hello "{ name }"!"
+            } else {
+                <i>"Nobody"</i>" is here"
+            }}
+            {if name.is_some() { <br /> }}
+        </p>
+    };
+    assert_eq!(greet(Some("<Peter>")).as_str(), "<p>Hello &lt;Peter&gt;!<br></p>");
+    assert_eq!(greet(None).as_str(), "<p><i>Nobody</i> is here</p>");
+}
+
+#[test]
+fn loops() {
+    let rows = vec![("a", 1), ("<b>", 2)];
+    let h = html! {
+        <table>
+            {for (name, value) in &rows {
+                <tr><td>{ name }</td><td>{ value }</td></tr>
+            }}
+        </table>
+    };
+    assert_eq!(
+        h.as_str(),
+        "<table><tr><td>a</td><td>1</td></tr><tr><td>&lt;b&gt;</td><td>2</td></tr></table>",
+    );
+}
+
+#[test]
+fn matches() {
+    let describe = |n: Option<u32>| html! {
+        <div>
+            {match n {
+                Some(0) => "zero",
+                Some(n) if n % 2 == 0 => <b>{ n }" is even"</b>,
+                Some(n) => <Greeting name="odd">{ n }</Greeting>,
+                None => <i>"none"</i>,
+            }}
+        </div>
+    };
+    assert_eq!(describe(Some(0)).as_str(), "<div>zero</div>");
+    assert_eq!(describe(Some(4)).as_str(), "<div><b>4 is even</b></div>");
+    assert_eq!(describe(Some(3)).as_str(), "<div><p>Hello odd3</p></div>");
+    assert_eq!(describe(None).as_str(), "<div><i>none</i></div>");
+}
+
+#[test]
+fn attribute_values() {
+    let checkbox = |checked: bool, name: Option<String>| html! {
+        <input type="checkbox" checked={ checked } name={ name } tabindex={ -1 } />
+    };
+    assert_eq!(
+        checkbox(true, Some("a\"b".into())).as_str(),
+        r#"<input type="checkbox" checked="" name="a&quot;b" tabindex="-1">"#,
+    );
+    assert_eq!(
+        checkbox(false, None).as_str(),
+        r#"<input type="checkbox" tabindex="-1">"#,
+    );
+    let details = |open: bool| html! { <details open={ open } aria-expanded={ open } /> };
+    assert_eq!(details(true).as_str(), r#"<details open="" aria-expanded="true"></details>"#);
+    assert_eq!(details(false).as_str(), r#"<details aria-expanded="false"></details>"#);
+}
+
+#[test]
+fn data_and_aria_attributes() {
+    let h = html! { <div role="alert" data-kind="error" aria-live="assertive">"!"</div> };
+    assert_eq!(
+        h.as_str(),
+        r#"<div role="alert" data-kind="error" aria-live="assertive">!</div>"#,
+    );
+    let h = html! { <button aria-pressed={ false } data-active={ false }>"B"</button> };
+    assert_eq!(h.as_str(), r#"<button aria-pressed="false" data-active="false">B</button>"#);
+}
+
+#[test]
+fn svg() {
+    let h = html! {
+        <svg viewBox="0 0 10 10">
+            <style>"circle { fill: red; }"</style>
+            <circle cx="5" cy="5" r="4" stroke-width="2" />
+            <foreignObject><p>"html"<br /></p></foreignObject>
+        </svg>
+    };
+    assert_eq!(
+        h.as_str(),
+        "<svg viewBox=\"0 0 10 10\"><style>circle { fill: red; }</style>\
+            <circle cx=\"5\" cy=\"5\" r=\"4\" stroke-width=\"2\"></circle>\
+            <foreignObject><p>html<br></p></foreignObject></svg>",
+    );
+}
+
+#[test]
+fn mathml() {
+    let h = html! {
+        <math><msqrt><mi>"x"</mi><mo>"<"</mo><mspace width="1em" /></msqrt></math>
+    };
+    assert_eq!(
+        h.as_str(),
+        r#"<math><msqrt><mi>x</mi><mo>&lt;</mo><mspace width="1em"></mspace></msqrt></math>"#,
+    );
+}
+
+#[test]
+fn custom_elements() {
+    let h = html! {
+        <p>"Hi "<user-name max-length={ 10 }>"Peter"</user-name></p>
+    };
+    assert_eq!(h.as_str(), r#"<p>Hi <user-name max-length="10">Peter</user-name></p>"#);
+}
+
+#[test]
+fn shadow_root() {
+    let h = html! {
+        <section>
+            <h2>"Light"</h2>
+            <#shadow mode="closed"><slot />{ "<b>" }</#shadow>
+        </section>
+    };
+    let expected = "<section><template shadowrootmode=\"closed\"><slot></slot>&lt;b&gt;\
+        </template><h2>Light</h2></section>";
+    assert_eq!(h.as_str(), expected);
+
+    let node = jsx!(Renderer => {
+        <section>
+            <h2>"Light"</h2>
+            <#shadow mode="closed"><slot />{ "<b>" }</#shadow>
+        </section>
+    });
+    assert_eq!(node.to_html().as_str(), expected);
+}
+
+#[test]
+fn properties_are_ignored() {
+    let h = html! { <textarea prop:value={ "text" }>"default"</textarea> };
+    assert_eq!(h.as_str(), "<textarea>default</textarea>");
+}
+
+#[test]
+fn spread_attributes() {
+    let attrs = vec![("id", "a\"b"), ("class", "spread"), ("aria-hidden", "true")];
+    let h = html! { <p class="fixed" {..attrs}>"x"</p> };
+    assert_eq!(h.as_str(), r#"<p class="fixed" id="a&quot;b" aria-hidden="true">x</p>"#);
+}
+
+#[test]
+fn class_directives() {
+    let selected = 1;
+    let h = html! {
+        <ul class="list">
+            {for i in 0..3 {
+                <li class:selected={ i == selected } class={ vec!["item"] }>{ i }</li>
+            }}
+        </ul>
+    };
+    assert_eq!(
+        h.as_str(),
+        "<ul class=\"list\"><li class=\"item\">0</li><li class=\"selected item\">1</li>\
+            <li class=\"item\">2</li></ul>",
+    );
+}
+
+#[test]
+fn style_directives() {
+    let color = "<red>";
+    let h = html! {
+        <p style="margin: 0" style:color={ color } style:background-color="blue">"Hi"</p>
+    };
+    assert_eq!(
+        h.as_str(),
+        "<p style=\"margin: 0; color: &lt;red&gt;; background-color: blue\">Hi</p>",
+    );
+}
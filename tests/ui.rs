@@ -0,0 +1,11 @@
+//! Tests for the compile errors of the macros. Every file in `tests/ui` has to
+//! fail to compile with the errors in the `.stderr` file of the same name.
+//! Run with `TRYBUILD=overwrite` to update those files.
+
+#![cfg(not(target_arch = "wasm32"))]
+
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
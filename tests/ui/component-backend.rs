@@ -0,0 +1,17 @@
+use domsl::{jsx, mock::Document};
+
+// Without a backend parameter, function components only work with `web_sys`.
+#[domsl::component]
+fn web_only() -> web_sys::HtmlDivElement {
+    unimplemented!()
+}
+
+#[domsl::component]
+fn wrong_backend_type<B: domsl::Backend>(#[domsl(backend)] backend: B) -> B::Node {
+    unimplemented!()
+}
+
+fn main() {
+    let d = Document::new();
+    jsx!(d => { <div><WebOnly /></div> });
+}
@@ -0,0 +1,32 @@
+// Component names that can't be derived from the function name.
+
+#[domsl::component]
+fn _1st() -> web_sys::HtmlDivElement {
+    unimplemented!()
+}
+
+#[domsl::component]
+fn self_() -> web_sys::HtmlDivElement {
+    unimplemented!()
+}
+
+#[domsl::component]
+fn __() -> web_sys::HtmlDivElement {
+    unimplemented!()
+}
+
+#[allow(non_snake_case)]
+#[domsl::component]
+fn Card() -> web_sys::HtmlDivElement {
+    unimplemented!()
+}
+
+// The derived name collides with an existing item.
+struct UserCard;
+
+#[domsl::component]
+fn user_card() -> web_sys::HtmlDivElement {
+    unimplemented!()
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use domsl::{jsx, mock::Document};
+
+fn main() {
+    let d = Document::new();
+
+    jsx!(d => { <ul><div /></ul> });
+    jsx!(d => { <p><div /></p> });
+    jsx!(d => { <table><tr><span /></tr></table> });
+    jsx!(d => { <ul>"text"</ul> });
+    jsx!(d => { <p><a><div /></a></p> });
+    jsx!(d => { <br><span /></br> });
+}
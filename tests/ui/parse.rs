@@ -0,0 +1,14 @@
+use domsl::{jsx, mock::Document};
+
+fn main() {
+    let d = Document::new();
+
+    jsx!(d => { <div></span> });
+    jsx!(d => { <div><p></div></p> });
+    jsx!(d => { <div class></div> });
+    jsx!(d => { <div class=x /> });
+    jsx!(d => { <div {attrs} /> });
+    jsx!(d => { <p /><p /> });
+    jsx!(d => { <> });
+    jsx!(d => { <#shade></#shade> });
+}
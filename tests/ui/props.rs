@@ -0,0 +1,43 @@
+use domsl::{jsx, mock::Document, Backend, Component, Props};
+
+#[derive(Props)]
+struct Button {
+    label: &'static str,
+    #[domsl(default)]
+    disabled: bool,
+}
+
+impl<B: Backend> Component<B> for Button {
+    type Node = B::Element<web_sys::HtmlButtonElement>;
+    fn render(self, backend: &B, _: Vec<B::Node>) -> Self::Node {
+        jsx!(backend => { <button disabled={ self.disabled }>{ self.label }</button> })
+    }
+}
+
+struct Plain {
+    label: &'static str,
+}
+
+impl<B: Backend> Component<B> for Plain {
+    type Node = B::Node;
+    fn render(self, backend: &B, _: Vec<B::Node>) -> Self::Node {
+        backend.create_text(self.label)
+    }
+}
+
+fn main() {
+    let d = Document::new();
+
+    // Missing prop.
+    jsx!(d => { <div><Button disabled={ true } /></div> });
+
+    // Duplicate prop.
+    jsx!(d => { <div><Button label="a" label="b" /></div> });
+
+    // Unknown prop.
+    jsx!(d => { <div><Button label="a" color={ 3 } /></div> });
+    jsx!(d => { <div><Button label="a" disable={ true } /></div> });
+
+    // Component without builder.
+    jsx!(d => { <div><Plain label="a" /></div> });
+}
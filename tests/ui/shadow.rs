@@ -0,0 +1,16 @@
+use domsl::{html, jsx, mock::Document};
+
+fn main() {
+    let d = Document::new();
+
+    // Void elements can't host a shadow root, in neither macro.
+    jsx!(d => { <div><input><#shadow>"x"</#shadow></input></div> });
+    html! { <div><input><#shadow>"x"</#shadow></input></div> };
+
+    // Neither can most other elements.
+    jsx!(d => { <ul><#shadow><slot /></#shadow></ul> });
+    html! { <ul><#shadow><slot /></#shadow></ul> };
+
+    // Only one shadow root per element.
+    html! { <div><#shadow /><#shadow /></div> };
+}